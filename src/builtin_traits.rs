@@ -0,0 +1,105 @@
+/// Known signatures of a base trait, used when the macro body does not restate them.
+pub struct KnownTrait {
+    pub path: syn::Path,
+    pub generic_params: Vec<syn::GenericParam>,
+    pub where_clause: Option<syn::WhereClause>,
+    pub methods: Vec<syn::TraitItemFn>,
+}
+
+impl KnownTrait {
+    pub fn new(path: syn::Path, item: syn::ItemTrait) -> Self {
+        Self {
+            path,
+            generic_params: item.generics.params.into_iter().collect(),
+            where_clause: item.generics.where_clause,
+            methods: item
+                .items
+                .into_iter()
+                .filter_map(|item| match item {
+                    syn::TraitItem::Fn(method) => Some(method),
+                    _ => None,
+                })
+                .collect(),
+        }
+    }
+
+    pub fn method(&self, ident: &syn::Ident) -> Option<syn::TraitItemFn> {
+        self.methods.iter().find(|method| method.sig.ident == *ident).cloned()
+    }
+}
+
+const BUILTIN_TRAITS: &[(&str, &str, &str)] = &[
+    (
+        "Clone",
+        "::core::clone::Clone",
+        "trait Clone { fn clone(&self) -> Self; }",
+    ),
+    (
+        "Default",
+        "::core::default::Default",
+        "trait Default { fn default() -> Self; }",
+    ),
+    (
+        "Display",
+        "::core::fmt::Display",
+        "trait Display { fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result; }",
+    ),
+    (
+        "Debug",
+        "::core::fmt::Debug",
+        "trait Debug { fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result; }",
+    ),
+    (
+        "ToString",
+        "::std::string::ToString",
+        "trait ToString { fn to_string(&self) -> ::std::string::String; }",
+    ),
+    (
+        "Hash",
+        "::core::hash::Hash",
+        "trait Hash { fn hash<H: ::core::hash::Hasher>(&self, state: &mut H); }",
+    ),
+    (
+        "PartialEq",
+        "::core::cmp::PartialEq",
+        "trait PartialEq<Rhs: ?Sized> { fn eq(&self, other: &Rhs) -> bool; }",
+    ),
+    (
+        "Into",
+        "::core::convert::Into",
+        "trait Into<T> { fn into(self) -> T; }",
+    ),
+    (
+        "AsRef",
+        "::core::convert::AsRef",
+        "trait AsRef<T: ?Sized> { fn as_ref<'a>(&'a self) -> &'a T where Self: 'a, T: 'a; }",
+    ),
+    (
+        "Iterator",
+        "::core::iter::Iterator",
+        "trait Iterator { fn next(&mut self) -> ::core::option::Option<<Self as ::core::iter::Iterator>::Item>; }",
+    ),
+];
+
+/// Looks up signatures of common `std`/`core` traits by the trait path given in the macro header.
+///
+/// Paths are matched by their last segment, as long as they are either a single identifier
+/// or start with `std`, `core` or `alloc`.
+pub fn builtin_trait(path: &syn::Path) -> Option<KnownTrait> {
+    let first = path.segments.first()?;
+    let last = path.segments.last()?;
+    if path.segments.len() > 1
+        && !["std", "core", "alloc"].iter().any(|krate| first.ident == krate)
+    {
+        return None;
+    }
+
+    let (_, canonical_path, source) = BUILTIN_TRAITS
+        .iter()
+        .find(|(name, _, _)| last.ident == name)?;
+
+    Some(KnownTrait::new(
+        syn::parse_str(canonical_path).expect("Built-in trait paths must parse."),
+        syn::parse_str(source).expect("Built-in trait signatures must parse."),
+    ))
+}
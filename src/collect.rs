@@ -0,0 +1,89 @@
+//! Companions collecting outputs that are the same for every element.
+
+use crate::companion::{Companion, CompanionBody, CompanionContext};
+
+/// `_vec`, `_array` and `_iter`, with `_vec` only generated when requested.
+///
+/// `_array` fills a local array of `Option`s head to tail, so it needs no allocation.
+///
+/// `_iter` evaluates one element per pulled item through the `_at_index` variant,
+/// so it is only generated when the list can be borrowed between calls.
+pub fn collect(ctx: &CompanionContext, at_index: Option<&syn::Ident>, requested_vec: bool) -> Vec<Companion> {
+    let CompanionContext { output, head, tail, this, args, args_cloned, .. } = ctx;
+    if ctx.output_mentions_self || matches!(ctx.sig.output, syn::ReturnType::Default) {
+        return vec![];
+    }
+    let (extend, fill) = (ctx.ident("__extend_"), ctx.ident("__fill_"));
+    let (index, other, value) = (crate::hygiene::index(), crate::hygiene::other(), crate::hygiene::value());
+    let n = crate::hygiene::n();
+    let mut companions = vec![];
+    if requested_vec {
+        companions.extend([
+            ctx.companion(
+                "__extend_",
+                CompanionBody::Recursive {
+                    nil: quote::quote!(),
+                    cons: quote::quote! {
+                        #other.push(#head);
+                        #tail #extend(#(#args,)* #other)
+                    },
+                },
+            )
+            .with_input(syn::parse_quote!(#other: &mut ::std::vec::Vec<#output>))
+            .with_output(syn::ReturnType::Default),
+            ctx.suffixed(
+                "_vec",
+                CompanionBody::Provided(quote::quote! {
+                    let mut #other = ::std::vec::Vec::with_capacity(Self::LEN);
+                    #this #extend(#(#args,)* &mut #other);
+                    #other
+                }),
+            )
+            .with_output(syn::parse_quote!(-> ::std::vec::Vec<#output>)),
+        ]);
+    }
+    companions.extend([
+        ctx.companion(
+            "__fill_",
+            CompanionBody::Recursive {
+                nil: quote::quote!(),
+                cons: quote::quote! {
+                    #other[0] = ::core::option::Option::Some(#head);
+                    #tail #fill(#(#args,)* &mut #other[1..])
+                },
+            },
+        )
+        .with_input(syn::parse_quote!(#other: &mut [::core::option::Option<#output>]))
+        .with_output(syn::ReturnType::Default),
+        ctx.suffixed(
+            "_array",
+            CompanionBody::Provided(quote::quote! {
+                const {
+                    assert!(
+                        #n == Self::LEN,
+                        "The array length has to match the length of the list."
+                    )
+                };
+                let mut #other: [::core::option::Option<#output>; #n] =
+                    ::core::array::from_fn(|_| ::core::option::Option::None);
+                #this #fill(#(#args,)* &mut #other);
+                #other.map(|#value| #value.expect("Every slot is filled by the list."))
+            }),
+        )
+        .with_generic_params([syn::parse_quote!(const #n: usize)])
+        .with_output(syn::parse_quote!(-> [#output; #n])),
+    ]);
+
+    if let Some(at_index) = at_index.filter(|_| ctx.can_call_repeatedly()) {
+        companions.push(
+            ctx.suffixed(
+                "_iter",
+                CompanionBody::Provided(quote::quote! {
+                    (0..Self::LEN).map(move |#index| #this #at_index(#(#args_cloned,)* #index))
+                }),
+            )
+            .with_output(syn::parse_quote!(-> impl Iterator<Item = #output>)),
+        );
+    }
+    companions
+}
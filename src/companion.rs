@@ -0,0 +1,271 @@
+//! Additional methods generated next to the elementwise one, like `sum_<m>` or `max_<m>`.
+
+#[derive(Clone)]
+pub struct Companion {
+    pub ident: syn::Ident,
+    /// Name of the companion in `#[hlist(...)]`, e.g. `sum` or `flat_iter`.
+    pub key: String,
+    /// Used in error messages, e.g. "`sum_` variant".
+    pub description: String,
+    pub sig: syn::Signature,
+    /// Helpers used by other companions are `#[doc(hidden)]`.
+    pub hidden: bool,
+    /// Recursive helpers meant to be flattened into their caller are `#[inline(always)]`.
+    pub inline: bool,
+    pub body: CompanionBody,
+    /// Associated types used in the signature, like `TRY_VALIDATEHListOutput`.
+    pub assoc_types: Vec<AssocType>,
+}
+
+/// A generic associated type with one definition for `Nil` and one for `Cons`.
+#[derive(Clone)]
+pub struct AssocType {
+    pub ident: syn::Ident,
+    pub generic_params: Vec<syn::GenericParam>,
+    pub where_clause: Option<syn::WhereClause>,
+    pub nil: syn::Type,
+    pub cons: syn::Type,
+}
+
+impl AssocType {
+    fn trait_item(&self) -> proc_macro2::TokenStream {
+        let Self { ident, generic_params, where_clause, .. } = self;
+        quote::quote! { type #ident<#(#generic_params),*> #where_clause; }
+    }
+
+    fn impl_item(&self, ty: &syn::Type) -> proc_macro2::TokenStream {
+        let Self { ident, generic_params, where_clause, .. } = self;
+        quote::quote! { type #ident<#(#generic_params),*> = #ty #where_clause; }
+    }
+}
+
+#[derive(Clone)]
+pub enum CompanionBody {
+    /// Implemented once in the HList trait, on top of other methods.
+    Provided(proc_macro2::TokenStream),
+    /// Implemented for `Nil` and `Cons`. The `Cons` body has `__hlist_head` and
+    /// `__hlist_tail` in scope when the method has a receiver.
+    Recursive {
+        nil: proc_macro2::TokenStream,
+        cons: proc_macro2::TokenStream,
+    },
+}
+
+impl Companion {
+    /// Whether the body or signature refers to `ident`, e.g. to call another companion.
+    pub fn mentions(&self, ident: &str) -> bool {
+        let body = match &self.body {
+            CompanionBody::Provided(body) => body.clone(),
+            CompanionBody::Recursive { nil, cons } => quote::quote!(#nil #cons),
+        };
+        crate::replace_self::mentions_ident(body, ident)
+    }
+
+    pub fn trait_item(&self) -> proc_macro2::TokenStream {
+        let sig = &self.sig;
+        let hidden = self.hidden.then(|| quote::quote!(#[doc(hidden)]));
+        let assoc_types = self.assoc_types.iter().map(AssocType::trait_item);
+        match &self.body {
+            CompanionBody::Provided(body) => quote::quote! { #(#assoc_types)* #hidden #sig { #body } },
+            CompanionBody::Recursive { .. } => quote::quote! { #(#assoc_types)* #hidden #sig; },
+        }
+    }
+
+    pub fn nil_item(&self) -> Option<proc_macro2::TokenStream> {
+        let sig = &self.sig;
+        let inline = self.inline_attr();
+        let assoc_types = self.assoc_types.iter().map(|assoc| assoc.impl_item(&assoc.nil));
+        match &self.body {
+            CompanionBody::Provided(_) => None,
+            CompanionBody::Recursive { nil, .. } => Some(quote::quote! { #(#assoc_types)* #inline #sig { #nil } }),
+        }
+    }
+
+    pub fn cons_item(&self, split_self: &proc_macro2::TokenStream) -> Option<proc_macro2::TokenStream> {
+        let sig = &self.sig;
+        let inline = self.inline_attr();
+        let assoc_types = self.assoc_types.iter().map(|assoc| assoc.impl_item(&assoc.cons));
+        match &self.body {
+            CompanionBody::Provided(_) => None,
+            CompanionBody::Recursive { cons, .. } => {
+                Some(quote::quote! { #(#assoc_types)* #inline #sig { #split_self #cons } })
+            }
+        }
+    }
+
+    fn inline_attr(&self) -> Option<proc_macro2::TokenStream> {
+        self.inline.then(|| quote::quote!(#[inline(always)]))
+    }
+}
+
+/// What companions need to know about the listed method.
+pub struct CompanionContext<'a> {
+    /// The listed signature.
+    pub sig: &'a syn::Signature,
+    /// Name of the elementwise method, after renaming.
+    pub name: &'a syn::Ident,
+    /// Output of the base trait method, with `Self` replaced by `__HListHead`.
+    pub output: &'a syn::Type,
+    /// Whether the listed output mentions `Self`.
+    pub output_mentions_self: bool,
+    /// Call of the base trait method on the head, cloning owned arguments for the tail.
+    pub head: proc_macro2::TokenStream,
+    /// Call of the base trait method on the head, moving the arguments.
+    /// Used when the tail is called first.
+    pub head_last: proc_macro2::TokenStream,
+    /// Prefix for calling methods on the tail: `__hlist_tail.` or `__HListTail::`.
+    pub tail: &'a proc_macro2::TokenStream,
+    /// Prefix for calling methods on the list itself: `self.` or `Self::`.
+    pub this: proc_macro2::TokenStream,
+    pub args: &'a [proc_macro2::TokenStream],
+    pub args_cloned: &'a [proc_macro2::TokenStream],
+    /// Generic parameters, arguments and `where` clause of the listed method,
+    /// repeated on associated types of companions.
+    pub generic_params: &'a [syn::GenericParam],
+    pub generic_args: &'a [syn::GenericArgument],
+    pub where_clause: Option<&'a syn::WhereClause>,
+    /// Names chosen with `#[hlist(<companion> = name)]` or naming templates.
+    pub options: &'a crate::options::HListOptions,
+}
+
+impl CompanionContext<'_> {
+    /// Name of the companion `{prefix}{name}`, unless renamed.
+    pub fn ident(&self, prefix: &str) -> syn::Ident {
+        self.affixed_ident(prefix, "")
+    }
+
+    /// Name of the companion `{name}{suffix}`, unless renamed.
+    pub fn suffixed_ident(&self, suffix: &str) -> syn::Ident {
+        self.affixed_ident("", suffix)
+    }
+
+    /// Name of the companion `{prefix}{name}{suffix}`, unless renamed.
+    pub fn affixed_ident(&self, prefix: &str, suffix: &str) -> syn::Ident {
+        self.options
+            .name(&key(prefix, suffix), self.name)
+            .unwrap_or_else(|| quote::format_ident!("{}{}{}", prefix, self.name, suffix))
+    }
+
+    /// Starts a companion named `{prefix}{name}` with the listed signature.
+    pub fn companion(&self, prefix: &str, body: CompanionBody) -> Companion {
+        self.affixed(prefix, "", body)
+    }
+
+    /// Starts a companion named `{name}{suffix}` with the listed signature.
+    pub fn suffixed(&self, suffix: &str, body: CompanionBody) -> Companion {
+        self.affixed("", suffix, body)
+    }
+
+    /// Starts a companion named `{prefix}{name}{suffix}` with the listed signature.
+    pub fn affixed(&self, prefix: &str, suffix: &str, body: CompanionBody) -> Companion {
+        let description = match (prefix, suffix) {
+            (_, "") => format!("`{prefix}` variant"),
+            ("", _) => format!("`{suffix}` variant"),
+            _ => format!("`{prefix}..{suffix}` variant"),
+        };
+        let mut sig = syn::Signature { ident: self.affixed_ident(prefix, suffix), ..self.sig.clone() };
+        // Default bodies taking `self` by value need a sized `Self`.
+        if let CompanionBody::Provided(_) = body {
+            sig.generics.make_where_clause().predicates.push(syn::parse_quote!(Self: Sized));
+        }
+        Companion {
+            ident: sig.ident.clone(),
+            key: key(prefix, suffix),
+            description,
+            sig,
+            hidden: prefix.starts_with("__"),
+            inline: false,
+            body,
+            assoc_types: vec![],
+        }
+    }
+
+    /// Whether the listed method can be called repeatedly from a closure borrowing the list,
+    /// which rules out `self` receivers and `&mut` arguments.
+    pub fn can_call_repeatedly(&self) -> bool {
+        let borrows_self = self.sig.receiver().is_none_or(|receiver| receiver.reference.is_some());
+        let mut_args = self.sig.inputs.iter().any(|input| {
+            matches!(input, syn::FnArg::Typed(arg) if matches!(&*arg.ty, syn::Type::Reference(ty) if ty.mutability.is_some()))
+        });
+        borrows_self && !mut_args
+    }
+
+    /// Bounds on the output type, omitted when the output has elided lifetimes,
+    /// which cannot be named in a `where` clause.
+    pub fn output_bounds(&self, bounds: proc_macro2::TokenStream) -> Option<syn::WherePredicate> {
+        let output = self.output;
+        (!crate::elided_lifetimes::has_elided_lifetime(quote::ToTokens::to_token_stream(output))).then(|| syn::parse_quote!(#output: #bounds))
+    }
+
+    /// An associated type named `{PREFIX}{METHOD}HListOutput`, with the generics of the listed method.
+    ///
+    /// `cons` is the type for the head, prepended to the same associated type of the tail.
+    /// `extra_params` are appended to the generics of the listed method.
+    pub fn assoc_type(&self, prefix: &str, cons: &syn::Type, extra_params: &[syn::GenericParam]) -> AssocType {
+        let ident = quote::format_ident!("{}{}HListOutput", prefix.to_uppercase(), self.sig.ident.to_string().to_uppercase());
+        self.assoc_type_named(ident, cons, extra_params)
+    }
+
+    pub fn assoc_type_named(&self, ident: syn::Ident, cons: &syn::Type, extra_params: &[syn::GenericParam]) -> AssocType {
+        let tail_ty = crate::hygiene::tail_ty();
+        let generic_args = self.generic_args.iter().cloned().chain(
+            extra_params.iter().cloned().map(crate::generic_param_to_arg::generic_param_to_arg),
+        );
+        AssocType {
+            cons: syn::parse_quote!(hlist2::Cons<#cons, #tail_ty::#ident<#(#generic_args),*>>),
+            nil: syn::parse_quote!(hlist2::Nil),
+            ident,
+            generic_params: self.generic_params.iter().chain(extra_params).cloned().collect(),
+            where_clause: self.where_clause.cloned(),
+        }
+    }
+
+    /// Panics unless the output is the same type for every element.
+    pub fn require_uniform_output(&self, companion: &str) {
+        assert!(
+            !self.output_mentions_self,
+            "`{companion}` of `{}` requires an output type that does not depend on `Self`.",
+            self.sig.ident
+        );
+    }
+}
+
+/// The key of a companion in `#[hlist(...)]`: `sum_` becomes `sum`, `flat_.._iter` becomes `flat_iter`.
+/// Helpers keep their leading underscores, so they can't be selected.
+fn key(prefix: &str, suffix: &str) -> String {
+    let hidden = if prefix.starts_with("__") { "__" } else { "" };
+    let parts: Vec<_> = [prefix, suffix].iter().map(|part| part.trim_matches('_')).filter(|part| !part.is_empty()).collect();
+    format!("{hidden}{}", parts.join("_"))
+}
+
+impl Companion {
+    pub fn inlined(mut self) -> Self {
+        self.inline = true;
+        self
+    }
+
+    pub fn with_assoc_type(mut self, assoc_type: AssocType) -> Self {
+        self.assoc_types.push(assoc_type);
+        self
+    }
+
+    pub fn with_output(mut self, output: syn::ReturnType) -> Self {
+        self.sig.output = output;
+        self
+    }
+
+    pub fn with_generic_params(mut self, params: impl IntoIterator<Item = syn::GenericParam>) -> Self {
+        self.sig.generics.params.extend(params);
+        self
+    }
+
+    pub fn with_input(mut self, input: syn::FnArg) -> Self {
+        self.sig.inputs.push(input);
+        self
+    }
+
+    pub fn with_where_predicates(mut self, predicates: impl IntoIterator<Item = syn::WherePredicate>) -> Self {
+        self.sig.generics.make_where_clause().predicates.extend(predicates);
+        self
+    }
+}
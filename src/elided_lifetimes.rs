@@ -0,0 +1,109 @@
+//! Naming of lifetimes elided in the output and in zipped arguments, which the associated types
+//! of the HList trait can't leave out.
+
+/// Rewrites `fn m(&self) -> &str` into
+/// `fn m<'__hlist_self>(&'__hlist_self self) -> &'__hlist_self str where Self: '__hlist_self`.
+///
+/// Only outputs borrowing from `&self` or `&mut self` are rewritten, following the elision rules.
+pub fn name_elided_output_lifetimes(sig: &mut syn::Signature) {
+    let syn::ReturnType::Type(_, output) = &sig.output else { return };
+    let Some(syn::FnArg::Receiver(receiver)) = sig.inputs.first_mut() else { return };
+    let Some((_, receiver_lifetime)) = &mut receiver.reference else { return };
+    let output = quote::ToTokens::to_token_stream(output);
+    if !has_elided_lifetime(output.clone()) {
+        return;
+    }
+
+    let lifetime = match receiver_lifetime {
+        Some(lifetime) => lifetime.clone(),
+        None => {
+            let lifetime = syn::Lifetime::new("'__hlist_self", proc_macro2::Span::call_site());
+            *receiver_lifetime = Some(lifetime.clone());
+            sig.generics.params.insert(0, syn::parse_quote!(#lifetime));
+            sig.generics.make_where_clause().predicates.push(syn::parse_quote!(Self: #lifetime));
+            lifetime
+        }
+    };
+    receiver.ty = {
+        let mutability = receiver.mutability;
+        syn::parse_quote!(&#lifetime #mutability Self)
+    };
+    let output = name_elided(output, &lifetime);
+    sig.output = syn::parse_quote!(-> #output);
+}
+
+/// Rewrites `buffers: &mut Buffer` into `buffers: &'__hlist_buffers mut Buffer`, for arguments
+/// whose type is repeated in an associated type of the HList trait.
+pub fn name_elided_argument_lifetimes(sig: &mut syn::Signature, arg: &syn::Ident) {
+    let lifetime = syn::Lifetime::new(&format!("'__hlist_{arg}"), proc_macro2::Span::call_site());
+    let mut named = false;
+    for input in sig.inputs.iter_mut() {
+        match input {
+            syn::FnArg::Typed(syn::PatType { pat, ty, .. }) if matches!(&**pat, syn::Pat::Ident(ident) if ident.ident == *arg) => {
+                let tokens = quote::ToTokens::to_token_stream(&**ty);
+                if has_elided_lifetime(tokens.clone()) {
+                    *ty = syn::parse2(name_elided(tokens, &lifetime)).expect("Naming lifetimes keeps the type valid.");
+                    named = true;
+                }
+            }
+            _ => {}
+        }
+    }
+    if named {
+        sig.generics.params.insert(0, syn::parse_quote!(#lifetime));
+    }
+}
+
+fn is_fn_sugar(tree: Option<&proc_macro2::TokenTree>) -> bool {
+    matches!(tree, Some(proc_macro2::TokenTree::Ident(ident)) if ident == "Fn" || ident == "FnMut" || ident == "FnOnce" || ident == "fn")
+}
+
+/// `&` without a lifetime or `'_`, outside of `Fn(..)` and `fn(..)` arguments, which elide on their own.
+pub fn has_elided_lifetime(tokens: proc_macro2::TokenStream) -> bool {
+    let trees: Vec<_> = tokens.into_iter().collect();
+    trees.iter().enumerate().any(|(i, tree)| match tree {
+        proc_macro2::TokenTree::Punct(punct) if punct.as_char() == '&' => {
+            !matches!(trees.get(i + 1), Some(proc_macro2::TokenTree::Punct(next)) if next.as_char() == '\'')
+        }
+        proc_macro2::TokenTree::Punct(punct) if punct.as_char() == '\'' => {
+            matches!(trees.get(i + 1), Some(proc_macro2::TokenTree::Ident(ident)) if ident == "_")
+        }
+        proc_macro2::TokenTree::Group(group) => {
+            !(i > 0 && is_fn_sugar(trees.get(i - 1))) && has_elided_lifetime(group.stream())
+        }
+        _ => false,
+    })
+}
+
+fn name_elided(tokens: proc_macro2::TokenStream, lifetime: &syn::Lifetime) -> proc_macro2::TokenStream {
+    let trees: Vec<_> = tokens.into_iter().collect();
+    let mut named = proc_macro2::TokenStream::new();
+    let mut skip_next = false;
+    for (i, tree) in trees.iter().enumerate() {
+        if std::mem::take(&mut skip_next) {
+            continue;
+        }
+        match tree {
+            proc_macro2::TokenTree::Punct(punct) if punct.as_char() == '&' => {
+                named.extend([tree.clone()]);
+                if !matches!(trees.get(i + 1), Some(proc_macro2::TokenTree::Punct(next)) if next.as_char() == '\'') {
+                    named.extend(quote::quote!(#lifetime));
+                }
+            }
+            proc_macro2::TokenTree::Punct(punct)
+                if punct.as_char() == '\''
+                    && matches!(trees.get(i + 1), Some(proc_macro2::TokenTree::Ident(ident)) if ident == "_") =>
+            {
+                named.extend(quote::quote!(#lifetime));
+                skip_next = true;
+            }
+            proc_macro2::TokenTree::Group(group) if !(i > 0 && is_fn_sugar(trees.get(i - 1))) => {
+                let mut replaced = proc_macro2::Group::new(group.delimiter(), name_elided(group.stream(), lifetime));
+                replaced.set_span(group.span());
+                named.extend([proc_macro2::TokenTree::Group(replaced)]);
+            }
+            other => named.extend([other.clone()]),
+        }
+    }
+    named
+}
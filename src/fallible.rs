@@ -0,0 +1,122 @@
+//! Companions short-circuiting on `Err` or `None` outputs.
+
+use crate::companion::{Companion, CompanionBody, CompanionContext};
+
+/// `try_` and `collect_errors_` for `Result` outputs, `all_some_` for `Option` outputs.
+///
+/// Errors are converted with `From` into `error_type`, if declared with `#[hlist(error = ...)]`,
+/// or else into `default_error_type` from the header, which methods without `Result` outputs ignore.
+pub fn fallible(
+    ctx: &CompanionContext,
+    error_type: Option<&syn::Type>,
+    default_error_type: Option<&syn::Type>,
+) -> Vec<Companion> {
+    let CompanionContext { output, head, tail, this, args, generic_args, .. } = ctx;
+    let (index, value, other) = (crate::hygiene::index(), crate::hygiene::value(), crate::hygiene::other());
+    let mut companions = vec![];
+
+    if let Some([ok, error]) = generic_args_of(output, "Result") {
+        // Without a declared error type, all elements have to fail with the same error.
+        let error = match error_type.or(default_error_type) {
+            Some(error_type) => Some(error_type),
+            None => (!crate::replace_self::mentions_ident(
+                quote::ToTokens::to_token_stream(error),
+                &crate::hygiene::head_ty().to_string(),
+            ))
+            .then_some(error),
+        };
+        if let Some(error) = error {
+            let (try_, collect) = (ctx.ident("try_"), ctx.ident("__collect_errors_"));
+            let assoc_type = ctx.assoc_type("try_", ok, &[]);
+            let assoc = &assoc_type.ident;
+            companions.push(
+                ctx.companion(
+                    "try_",
+                    CompanionBody::Recursive {
+                        nil: quote::quote!(core::result::Result::Ok(hlist2::Nil)),
+                        cons: quote::quote! {
+                            core::result::Result::Ok(hlist2::Cons(#head?, #tail #try_(#(#args),*)?))
+                        },
+                    },
+                )
+                .with_output(syn::parse_quote! {
+                    -> core::result::Result<Self::#assoc<#(#generic_args),*>, #error>
+                })
+                .with_assoc_type(assoc_type),
+            );
+            companions.push(
+                ctx.companion(
+                    "__collect_errors_",
+                    CompanionBody::Recursive {
+                        nil: quote::quote!(),
+                        cons: quote::quote! {
+                            if let core::result::Result::Err(#value) = #head {
+                                #other.push((#index, core::convert::From::from(#value)));
+                            }
+                            #tail #collect(#(#args,)* #index + 1, #other)
+                        },
+                    },
+                )
+                .with_input(syn::parse_quote!(#index: usize))
+                .with_input(syn::parse_quote!(#other: &mut ::std::vec::Vec<(usize, #error)>))
+                .with_output(syn::ReturnType::Default),
+            );
+            companions.push(
+                ctx.companion(
+                    "collect_errors_",
+                    CompanionBody::Provided(quote::quote! {
+                        let mut #other = ::std::vec::Vec::new();
+                        #this #collect(#(#args,)* 0, &mut #other);
+                        #other
+                    }),
+                )
+                .with_output(syn::parse_quote!(-> ::std::vec::Vec<(usize, #error)>)),
+            );
+        }
+    } else {
+        assert!(
+            error_type.is_none(),
+            "`#[hlist(error = ...)]` on `{}` requires a `Result` output type.",
+            ctx.sig.ident
+        );
+    }
+
+    if let Some([some]) = generic_args_of(output, "Option") {
+        let all_some = ctx.ident("all_some_");
+        let assoc_type = ctx.assoc_type("all_some_", some, &[]);
+        let assoc = &assoc_type.ident;
+        companions.push(
+            ctx.companion(
+                "all_some_",
+                CompanionBody::Recursive {
+                    nil: quote::quote!(core::option::Option::Some(hlist2::Nil)),
+                    cons: quote::quote! {
+                        core::option::Option::Some(hlist2::Cons(#head?, #tail #all_some(#(#args),*)?))
+                    },
+                },
+            )
+            .with_output(syn::parse_quote!(-> core::option::Option<Self::#assoc<#(#generic_args),*>>))
+            .with_assoc_type(assoc_type),
+        );
+    }
+    companions
+}
+
+/// Type arguments of `ty`, if its last path segment is `name` with exactly `N` of them.
+pub fn generic_args_of<'a, const N: usize>(ty: &'a syn::Type, name: &str) -> Option<[&'a syn::Type; N]> {
+    let syn::Type::Path(ty) = ty else { return None };
+    let last = ty.path.segments.last()?;
+    let syn::PathArguments::AngleBracketed(args) = &last.arguments else { return None };
+    if ty.qself.is_some() || last.ident != name {
+        return None;
+    }
+    let types: Vec<_> = args
+        .args
+        .iter()
+        .map(|arg| match arg {
+            syn::GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        })
+        .collect::<Option<_>>()?;
+    types.try_into().ok()
+}
@@ -0,0 +1,86 @@
+//! Companions flattening collection and hlist outputs into one sequence.
+
+use crate::companion::{Companion, CompanionBody, CompanionContext};
+
+/// `flat_` and `flat_.._iter` for `Vec` outputs, or other `IntoIterator` outputs with `#[hlist(flat)]`.
+/// `concat_` for hlist outputs with `#[hlist(concat)]`.
+pub fn flatten(
+    ctx: &CompanionContext,
+    at_index: Option<&syn::Ident>,
+    requested: impl Fn(&str) -> bool,
+) -> Vec<Companion> {
+    let CompanionContext { output, head, tail, this, args, args_cloned, generic_args, .. } = ctx;
+    let (index, other) = (crate::hygiene::index(), crate::hygiene::other());
+    let mut companions = vec![];
+
+    let item: Option<syn::Type> = if requested("flat") {
+        ctx.require_uniform_output("flat");
+        Some(syn::parse_quote!(<#output as ::core::iter::IntoIterator>::Item))
+    } else {
+        crate::fallible::generic_args_of::<1>(output, "Vec")
+            .filter(|_| !ctx.output_mentions_self)
+            .map(|[item]| item.clone())
+    };
+    if let Some(item) = item {
+        let extend = ctx.ident("__flat_extend_");
+        companions.push(
+            ctx.companion(
+                "__flat_extend_",
+                CompanionBody::Recursive {
+                    nil: quote::quote!(),
+                    cons: quote::quote! {
+                        ::core::iter::Extend::extend(#other, #head);
+                        #tail #extend(#(#args,)* #other)
+                    },
+                },
+            )
+            .with_input(syn::parse_quote!(#other: &mut ::std::vec::Vec<#item>))
+            .with_output(syn::ReturnType::Default)
+            .with_where_predicates(ctx.output_bounds(quote::quote!(::core::iter::IntoIterator))),
+        );
+        companions.push(
+            ctx.companion(
+                "flat_",
+                CompanionBody::Provided(quote::quote! {
+                    let mut #other = ::std::vec::Vec::new();
+                    #this #extend(#(#args,)* &mut #other);
+                    #other
+                }),
+            )
+            .with_output(syn::parse_quote!(-> ::std::vec::Vec<#item>))
+            .with_where_predicates(ctx.output_bounds(quote::quote!(::core::iter::IntoIterator))),
+        );
+        if let Some(at_index) = at_index.filter(|_| ctx.can_call_repeatedly()) {
+            companions.push(
+                ctx.affixed(
+                    "flat_",
+                    "_iter",
+                    CompanionBody::Provided(quote::quote! {
+                        (0..Self::LEN).flat_map(move |#index| #this #at_index(#(#args_cloned,)* #index))
+                    }),
+                )
+                .with_output(syn::parse_quote!(-> impl Iterator<Item = #item>))
+                .with_where_predicates(ctx.output_bounds(quote::quote!(::core::iter::IntoIterator))),
+            );
+        }
+    }
+
+    if requested("concat") {
+        let (name, hlist_output) = (
+            ctx.name,
+            quote::format_ident!("{}HListOutput", ctx.sig.ident.to_string().to_uppercase()),
+        );
+        let outputs: syn::Type = syn::parse_quote!(Self::#hlist_output<#(#generic_args),*>);
+        companions.push(
+            ctx.companion(
+                "concat_",
+                CompanionBody::Provided(quote::quote! {
+                    hlist2::ops::Flatten::flatten(#this #name(#(#args),*))
+                }),
+            )
+            .with_output(syn::parse_quote!(-> <#outputs as hlist2::ops::Flatten>::Output))
+            .with_where_predicates([syn::parse_quote!(#outputs: hlist2::ops::Flatten)]),
+        );
+    }
+    companions
+}
@@ -0,0 +1,107 @@
+//! Identifiers introduced by the expansion.
+//!
+//! They are created with [`proc_macro2::Span::mixed_site`], so that local variables
+//! never clash with argument names of listed methods.
+
+pub fn head() -> syn::Ident {
+    syn::Ident::new("__hlist_head", proc_macro2::Span::mixed_site())
+}
+
+pub fn tail() -> syn::Ident {
+    syn::Ident::new("__hlist_tail", proc_macro2::Span::mixed_site())
+}
+
+pub fn index() -> syn::Ident {
+    syn::Ident::new("__hlist_index", proc_macro2::Span::mixed_site())
+}
+
+pub fn remaining() -> syn::Ident {
+    syn::Ident::new("__hlist_remaining", proc_macro2::Span::mixed_site())
+}
+
+pub fn indices() -> syn::Ident {
+    syn::Ident::new("__hlist_indices", proc_macro2::Span::mixed_site())
+}
+
+pub fn indices_ty() -> syn::Ident {
+    syn::Ident::new("__HListIndices", proc_macro2::Span::mixed_site())
+}
+
+/// `{Method}Signature`, which shows up in signature mismatch errors.
+pub fn signature_ty(camel_case: &str) -> syn::Ident {
+    quote::format_ident!("{}Signature", camel_case, span = proc_macro2::Span::mixed_site())
+}
+
+pub fn head_ty() -> syn::Ident {
+    syn::Ident::new("__HListHead", proc_macro2::Span::mixed_site())
+}
+
+pub fn tail_ty() -> syn::Ident {
+    syn::Ident::new("__HListTail", proc_macro2::Span::mixed_site())
+}
+
+pub fn value() -> syn::Ident {
+    syn::Ident::new("__hlist_value", proc_macro2::Span::mixed_site())
+}
+
+pub fn other() -> syn::Ident {
+    syn::Ident::new("__hlist_other", proc_macro2::Span::mixed_site())
+}
+
+pub fn acc() -> syn::Ident {
+    syn::Ident::new("__hlist_acc", proc_macro2::Span::mixed_site())
+}
+
+pub fn acc_ty() -> syn::Ident {
+    syn::Ident::new("__HListAcc", proc_macro2::Span::mixed_site())
+}
+
+pub fn fold() -> syn::Ident {
+    syn::Ident::new("__hlist_fold", proc_macro2::Span::mixed_site())
+}
+
+pub fn fold_ty() -> syn::Ident {
+    syn::Ident::new("__HListFold", proc_macro2::Span::mixed_site())
+}
+
+pub fn index_ty() -> syn::Ident {
+    syn::Ident::new("__HListIndex", proc_macro2::Span::mixed_site())
+}
+
+pub fn n() -> syn::Ident {
+    syn::Ident::new("__HLIST_N", proc_macro2::Span::mixed_site())
+}
+
+pub fn writer() -> syn::Ident {
+    syn::Ident::new("__hlist_writer", proc_macro2::Span::mixed_site())
+}
+
+pub fn writer_ty() -> syn::Ident {
+    syn::Ident::new("__HListWriter", proc_macro2::Span::mixed_site())
+}
+
+pub fn separator() -> syn::Ident {
+    syn::Ident::new("__hlist_separator", proc_macro2::Span::mixed_site())
+}
+
+/// `__hlist_value{i}` and `__hlist_other{i}`, for tuple positions.
+pub fn numbered(ident: &syn::Ident, i: usize) -> syn::Ident {
+    syn::Ident::new(&format!("{ident}{i}"), proc_macro2::Span::mixed_site())
+}
+
+/// The values of a `#[hlist(zip)]` argument left for the tail.
+pub fn zip_tail(arg: &syn::Ident) -> syn::Ident {
+    syn::Ident::new(&format!("__hlist_{arg}_tail"), proc_macro2::Span::mixed_site())
+}
+
+pub fn listed() -> syn::Ident {
+    syn::Ident::new("__hlist_listed", proc_macro2::Span::mixed_site())
+}
+
+pub fn base() -> syn::Ident {
+    syn::Ident::new("__hlist_base", proc_macro2::Span::mixed_site())
+}
+
+pub fn signatures() -> syn::Ident {
+    syn::Ident::new("__hlist_signatures", proc_macro2::Span::mixed_site())
+}
@@ -0,0 +1,225 @@
+//! Companions evaluating single elements by their position in the list.
+
+use crate::companion::{Companion, CompanionBody, CompanionContext};
+
+/// `_at_index`, and `get_` when requested, for outputs that are the same for every element.
+///
+/// Both are provided on top of a hidden `__get_remaining_`, so either can be left out on its own.
+/// It looks for the element followed by `remaining - 1` others, comparing the same value against
+/// `Self::LEN` at each level. Once the `#[inline(always)]` levels are flattened, that is a `match`
+/// on constants, so every element is reached in the same time.
+pub fn at_index(ctx: &CompanionContext, requested_get: bool) -> Vec<Companion> {
+    let CompanionContext { output, head_last, tail, this, args, .. } = ctx;
+    if ctx.output_mentions_self {
+        return vec![];
+    }
+    let (at_index, get) = (ctx.suffixed_ident("_at_index"), ctx.ident("__get_remaining_"));
+    let (index, remaining, value) = (crate::hygiene::index(), crate::hygiene::remaining(), crate::hygiene::value());
+    let name = at_index.to_string();
+    let option: syn::ReturnType = syn::parse_quote!(-> core::option::Option<#output>);
+    let mut companions = vec![
+        ctx.companion(
+            "__get_remaining_",
+            CompanionBody::Recursive {
+                nil: quote::quote!(core::option::Option::None),
+                cons: quote::quote! {
+                    if #remaining == Self::LEN {
+                        core::option::Option::Some(#head_last)
+                    } else {
+                        #tail #get(#(#args,)* #remaining)
+                    }
+                },
+            },
+        )
+        .with_input(syn::parse_quote!(#remaining: usize))
+        .with_output(option.clone())
+        .inlined(),
+        ctx.suffixed(
+            "_at_index",
+            CompanionBody::Provided(quote::quote! {
+                match Self::LEN.checked_sub(#index).and_then(|#remaining| #this #get(#(#args,)* #remaining)) {
+                    core::option::Option::Some(#value) => #value,
+                    core::option::Option::None => panic!(
+                        "Index {} is out of bounds for `{}` on a list of length {}.",
+                        #index,
+                        #name,
+                        Self::LEN
+                    ),
+                }
+            }),
+        )
+        .with_input(syn::parse_quote!(#index: usize)),
+    ];
+    if requested_get {
+        companions.push(
+            ctx.companion(
+                "get_",
+                CompanionBody::Provided(quote::quote! {
+                    Self::LEN.checked_sub(#index).and_then(|#remaining| #this #get(#(#args,)* #remaining))
+                }),
+            )
+            .with_input(syn::parse_quote!(#index: usize))
+            .with_output(option),
+        );
+    }
+    companions
+}
+
+/// `_at_indices`, calling `_at_index` for every index of an iterator.
+///
+/// Each index goes through the constant time dispatch of `_at_index`, so the list is never walked.
+/// Outputs are collected into a `Vec` in the order of the indices, unless they are `()`.
+/// Not generated for `self` receivers, which can only be called once.
+pub fn at_indices(ctx: &CompanionContext, at_index: Option<&syn::Ident>) -> Option<Companion> {
+    let CompanionContext { output, this, .. } = ctx;
+    let at_index = at_index?;
+    if ctx.sig.receiver().is_some_and(|receiver| receiver.reference.is_none()) {
+        return None;
+    }
+    let (index, indices, indices_ty, other) = (
+        crate::hygiene::index(),
+        crate::hygiene::indices(),
+        crate::hygiene::indices_ty(),
+        crate::hygiene::other(),
+    );
+    // Arguments are passed again for every index: shared references are copied,
+    // mutable references reborrowed and owned values cloned.
+    let args = ctx.sig.inputs.iter().filter_map(|input| match input {
+        syn::FnArg::Typed(syn::PatType { pat, ty, .. }) => Some(match &**ty {
+            syn::Type::Reference(syn::TypeReference { mutability: Some(_), .. }) => quote::quote!(&mut *#pat),
+            syn::Type::Reference(_) => quote::quote!(#pat),
+            _ => quote::quote!(Clone::clone(&#pat)),
+        }),
+        syn::FnArg::Receiver(_) => None,
+    });
+    let call = quote::quote!(#this #at_index(#(#args,)* #index));
+    let companion = if matches!(ctx.sig.output, syn::ReturnType::Default) {
+        ctx.suffixed("_at_indices", CompanionBody::Provided(quote::quote! {
+            for #index in #indices {
+                #call;
+            }
+        }))
+    } else {
+        ctx.suffixed("_at_indices", CompanionBody::Provided(quote::quote! {
+            let #indices = ::core::iter::IntoIterator::into_iter(#indices);
+            let mut #other = ::std::vec::Vec::with_capacity(#indices.size_hint().0);
+            for #index in #indices {
+                #other.push(#call);
+            }
+            #other
+        }))
+        .with_output(syn::parse_quote!(-> ::std::vec::Vec<#output>))
+    };
+    Some(
+        companion
+            .with_generic_params([syn::parse_quote!(#indices_ty: ::core::iter::IntoIterator<Item = usize>)])
+            .with_input(syn::parse_quote!(#indices: #indices_ty)),
+    )
+}
+
+/// `_at::<I>`, calling the method on the element at a type-level index like
+/// `hlist2::ops::There<hlist2::ops::Here>`, and returning the output of that element.
+///
+/// Dispatches through the hidden trait from [`at_trait`], which is only implemented
+/// for indices within the list.
+pub fn at(ctx: &CompanionContext, at_trait: &syn::Ident, trait_generic_args: &[syn::GenericArgument]) -> Companion {
+    let CompanionContext { args, generic_args, .. } = ctx;
+    let index_ty = crate::hygiene::index_ty();
+    let at_trait: syn::Path = syn::parse_quote!(#at_trait<#(#trait_generic_args,)* #index_ty>);
+    let receiver = ctx.sig.receiver().map(|_| quote::quote!(self,));
+    let mut companion = ctx
+        .suffixed(
+            "_at",
+            CompanionBody::Provided(quote::quote!(<Self as #at_trait>::call(#receiver #(#args),*))),
+        )
+        .with_output(syn::parse_quote!(-> <Self as #at_trait>::Output<#(#generic_args),*>))
+        .with_where_predicates([syn::parse_quote!(Self: #at_trait)]);
+    // The index comes first in turbofishes, after lifetimes which can't be given there.
+    let lifetimes = companion.sig.generics.lifetimes().count();
+    companion.sig.generics.params.insert(lifetimes, syn::parse_quote!(#index_ty));
+    companion
+}
+
+/// The hidden trait behind `_at::<I>`, and its impls for `Cons` at `Here`, calling the method
+/// on the head, and at `There<I>`, looking up `I` in the tail.
+///
+/// The impls are returned apart, since they call through the `call_trait` of the method.
+pub fn at_trait(
+    vis: &Option<syn::Token![pub]>,
+    trait_generic_params: &[syn::GenericParam],
+    trait_where_clause: &Option<syn::WhereClause>,
+    base_trait: &syn::Path,
+    method: &crate::TraitHListMethod,
+) -> Option<(proc_macro2::TokenStream, proc_macro2::TokenStream)> {
+    let crate::TraitHListMethod {
+        at_trait,
+        item_sig,
+        item_output,
+        item_generic_params,
+        item_generic_args,
+        item_where_clause,
+        args,
+        head_fn,
+        head_self,
+        ..
+    } = method;
+    if !method.companions.iter().any(|companion| companion.key == "at") {
+        return None;
+    }
+    let (head, tail, head_ty, tail_ty, index_ty) = (
+        crate::hygiene::head(),
+        crate::hygiene::tail(),
+        crate::hygiene::head_ty(),
+        crate::hygiene::tail_ty(),
+        crate::hygiene::index_ty(),
+    );
+    let trait_generic_args: Vec<_> = trait_generic_params
+        .iter()
+        .cloned()
+        .map(crate::generic_param_to_arg::generic_param_to_arg)
+        .collect();
+    let call = syn::Signature {
+        ident: syn::parse_quote!(call),
+        output: syn::parse_quote!(-> Self::Output<#(#item_generic_args),*>),
+        ..item_sig.clone()
+    };
+    let (split_head, split_tail, tail_receiver) = match item_sig.receiver() {
+        Some(_) => (
+            quote::quote!(let hlist2::Cons(#head, _) = self;),
+            quote::quote!(let hlist2::Cons(_, #tail) = self;),
+            quote::quote!(#tail,),
+        ),
+        None => (quote::quote!(), quote::quote!(), quote::quote!()),
+    };
+
+    let definition = quote::quote! {
+        #[doc(hidden)]
+        #vis trait #at_trait<#(#trait_generic_params,)* #index_ty> #trait_where_clause {
+            type Output<#(#item_generic_params),*> #item_where_clause;
+            #call;
+        }
+    };
+    let impls = quote::quote! {
+        impl<#(#trait_generic_params,)* #head_ty: #base_trait, #tail_ty>
+            #at_trait<#(#trait_generic_args,)* hlist2::ops::Here> for hlist2::Cons<#head_ty, #tail_ty> #trait_where_clause
+        {
+            type Output<#(#item_generic_params),*> = #item_output #item_where_clause;
+            #call {
+                #split_head
+                #head_fn(#head_self #(#args),*)
+            }
+        }
+
+        impl<#(#trait_generic_params,)* #head_ty, #tail_ty: #at_trait<#(#trait_generic_args,)* #index_ty>, #index_ty: hlist2::ops::Index>
+            #at_trait<#(#trait_generic_args,)* hlist2::ops::There<#index_ty>> for hlist2::Cons<#head_ty, #tail_ty> #trait_where_clause
+        {
+            type Output<#(#item_generic_params),*> =
+                <#tail_ty as #at_trait<#(#trait_generic_args,)* #index_ty>>::Output<#(#item_generic_args),*> #item_where_clause;
+            #call {
+                #split_tail
+                <#tail_ty as #at_trait<#(#trait_generic_args,)* #index_ty>>::call(#tail_receiver #(#args),*)
+            }
+        }
+    };
+    Some((definition, impls))
+}
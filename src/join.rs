@@ -0,0 +1,88 @@
+//! Companions formatting the outputs of all elements with a separator.
+
+use crate::companion::{Companion, CompanionBody, CompanionContext};
+
+/// `write_` and `join_`, generated for string outputs or when requested with `#[hlist(join)]`.
+pub fn join(ctx: &CompanionContext, requested: bool) -> Vec<Companion> {
+    let CompanionContext { output, head, tail, this, args, .. } = ctx;
+    if !requested && !is_string(output) {
+        return vec![];
+    }
+    ctx.require_uniform_output("join");
+    let (write, write_after) = (ctx.ident("write_"), ctx.ident("__write_after_"));
+    let (writer, writer_ty, separator) = (
+        crate::hygiene::writer(),
+        crate::hygiene::writer_ty(),
+        crate::hygiene::separator(),
+    );
+    let display = ctx.output_bounds(quote::quote!(core::fmt::Display));
+    let with_writer = |companion: Companion| {
+        companion
+            .with_generic_params([syn::parse_quote!(#writer_ty: core::fmt::Write + ?Sized)])
+            .with_input(syn::parse_quote!(#writer: &mut #writer_ty))
+            .with_input(syn::parse_quote!(#separator: &str))
+            .with_output(syn::parse_quote!(-> core::fmt::Result))
+            .with_where_predicates(display.clone())
+    };
+
+    vec![
+        with_writer(ctx.companion(
+            "write_",
+            CompanionBody::Recursive {
+                nil: quote::quote!(core::result::Result::Ok(())),
+                cons: quote::quote! {
+                    core::write!(#writer, "{}", #head)?;
+                    #tail #write_after(#(#args,)* #writer, #separator)
+                },
+            },
+        )),
+        with_writer(ctx.companion(
+            "__write_after_",
+            CompanionBody::Recursive {
+                nil: quote::quote!(core::result::Result::Ok(())),
+                cons: quote::quote! {
+                    #writer.write_str(#separator)?;
+                    core::write!(#writer, "{}", #head)?;
+                    #tail #write_after(#(#args,)* #writer, #separator)
+                },
+            },
+        )),
+        ctx.companion(
+            "join_",
+            CompanionBody::Provided(quote::quote! {
+                let mut #writer = ::std::string::String::new();
+                core::result::Result::expect(
+                    #this #write(#(#args,)* &mut #writer, #separator),
+                    "a Display implementation returned an error unexpectedly",
+                );
+                #writer
+            }),
+        )
+        .with_input(syn::parse_quote!(#separator: &str))
+        .with_output(syn::parse_quote!(-> ::std::string::String))
+        .with_where_predicates(display.clone()),
+    ]
+}
+
+/// `String`, `&str`, `Box<str>`, `Rc<str>`, `Arc<str>` and `Cow<str>`.
+fn is_string(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Reference(ty) => is_str(&ty.elem),
+        syn::Type::Path(ty) if ty.qself.is_none() => {
+            let Some(last) = ty.path.segments.last() else { return false };
+            match &last.arguments {
+                syn::PathArguments::None => last.ident == "String",
+                syn::PathArguments::AngleBracketed(args) => {
+                    ["Box", "Rc", "Arc", "Cow"].iter().any(|name| last.ident == name)
+                        && args.args.iter().any(|arg| matches!(arg, syn::GenericArgument::Type(ty) if is_str(ty)))
+                }
+                syn::PathArguments::Parenthesized(_) => false,
+            }
+        }
+        _ => false,
+    }
+}
+
+fn is_str(ty: &syn::Type) -> bool {
+    matches!(ty, syn::Type::Path(ty) if ty.qself.is_none() && ty.path.is_ident("str"))
+}
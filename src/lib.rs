@@ -106,6 +106,24 @@ mod trait_hlist_input;
 mod trait_hlist_method;
 mod angle_bracketed_generic_params;
 mod generic_param_to_arg;
+mod replace_self;
+mod builtin_traits;
+mod trait_hlist_export;
+mod signature_check;
+mod hygiene;
+mod companion;
+mod reductions;
+mod search;
+mod fallible;
+mod collect;
+mod elided_lifetimes;
+mod join;
+mod unzip;
+mod flatten;
+mod options;
+mod indexing;
+mod rev;
+mod per_element;
 
 use trait_hlist_input::TraitHListInput;
 use trait_hlist_method::TraitHListMethod;
@@ -189,8 +207,47 @@ use angle_bracketed_generic_params::AngleBracketedGenericParams;
 /// - For methods that return bool, macro also provides:
 ///   - `.all_<method>()` — returns `true` if all results are `true`.
 ///   - `.any_<method>()` — returns `true` if any result is `true`.
+///   - `.none_<method>()` — returns `true` if no result is `true`.
+///   - `.count_<method>()` — counts the `true` results.
+///   - `.exactly_one_<method>()` — returns `true` if exactly one result is `true`.
+///   - `.mask_<method>()` — a `u64` with bit `i` set for a `true` result of element `i`,
+///     for lists of up to 64 elements. Longer lists fail to compile when it is called.
+///   - `.position_<method>()` and `.rposition_<method>()` — index of the first `true` result
+///     from the front or from the back, without evaluating the elements after it.
 ///
-///   `.all_*` and `.any_*` methods are lazily evaluated from head to tail.
+///   `.all_*`, `.any_*`, `.none_*` and `.position_*` methods are lazily evaluated from head to tail.
+/// - For methods that return `Option<T>`, `.find_map_<method>()` returns the first `Some`
+///   without evaluating later elements.
+///
+/// The length limit of `.mask_*` is checked when the program is built:
+/// ```rust,compile_fail,E0080
+/// use hlist2::hlist;
+/// use hlist2_trait_macro::TraitHList;
+///
+/// trait Flag {
+///     fn is_set(&self) -> bool;
+/// }
+/// impl Flag for bool {
+///     fn is_set(&self) -> bool {
+///         *self
+///     }
+/// }
+///
+/// TraitHList! {
+///     FlagHList for trait Flag {
+///         fn is_set(&self) -> bool;
+///     }
+/// }
+///
+/// let flags = hlist![
+///     true, true, true, true, true, true, true, true, true, true, true, true, true,
+///     true, true, true, true, true, true, true, true, true, true, true, true, true,
+///     true, true, true, true, true, true, true, true, true, true, true, true, true,
+///     true, true, true, true, true, true, true, true, true, true, true, true, true,
+///     true, true, true, true, true, true, true, true, true, true, true, true, true
+/// ];
+/// let mask = flags.mask_is_set(); // 65 elements don't fit into a `u64`
+/// ```
 ///
 /// ## Renaming Methods
 ///
@@ -215,6 +272,43 @@ use angle_bracketed_generic_params::AngleBracketedGenericParams;
 ///
 /// This generates a method `hlist_into` instead of the default `into`.
 ///
+/// To rename every method of a trait at once, give a naming template in front of the header,
+/// where `{}` stands for the method name. Companions can be given templates the same way,
+/// applied to the renamed method:
+///
+/// ```rust
+/// use hlist2::hlist;
+/// use hlist2_trait_macro::TraitHList;
+///
+/// TraitHList! {
+///     #[hlist(rename = "hlist_{}", at_index = "{}_at")]
+///     ToStringHList for trait ToString;
+/// }
+///
+/// let list = hlist![1u8, 2u16];
+/// assert_eq!(list.hlist_to_string(), hlist![String::from("1"), String::from("2")]);
+/// assert_eq!(list.hlist_to_string_at(1), "2");
+/// ```
+///
+/// `#[name = ...]` and names given on a method take precedence over the templates.
+///
+/// Names generated for different methods must not collide, which is checked by the macro:
+/// ```rust,compile_fail
+/// use hlist2_trait_macro::TraitHList;
+///
+/// trait Check {
+///     fn valid(&self) -> bool;
+///     fn all_valid(&self) -> bool;
+/// }
+///
+/// TraitHList! {
+///     CheckHList for trait Check {
+///         fn valid(&self) -> bool;
+///         fn all_valid(&self) -> bool; // collides with the `all_` variant generated for `valid`
+///     }
+/// }
+/// ```
+///
 /// ## Generic Traits
 ///TraitHList
 /// ```rust
@@ -260,6 +354,708 @@ use angle_bracketed_generic_params::AngleBracketedGenericParams;
 /// Also note, that parameters passed by value must implement either `Copy` or `Clone`, 
 /// because they are passed to each element of the list.
 ///
+/// ## Standard Traits
+///
+/// Signatures of common `std` traits are known to the macro, so they don't have to be restated:
+/// `Clone`, `Default`, `Display`, `Debug`, `ToString`, `Hash`, `PartialEq`, `Into`, `AsRef` and `Iterator`.
+/// A body-less definition lifts all known methods, while `fn <name>;` lifts a single one
+/// and allows renaming methods that clash with the trait of the list itself.
+/// ```rust
+/// use hlist2::{hlist, HList};
+/// use hlist2_trait_macro::TraitHList;
+///
+/// TraitHList! { ToStringHList for trait ToString; }
+/// TraitHList! { DefaultHList for trait Default; }
+/// TraitHList! {
+///     CloneHList for trait Clone {
+///         #[name = clone_each]
+///         fn clone;
+///     }
+/// }
+///
+/// let list = hlist![1, 2.5, 'c'];
+/// assert_eq!(hlist![String::from("1"), String::from("2.5"), String::from("c")], list.to_string());
+/// assert_eq!(list, list.clone_each());
+/// assert_eq!(hlist![0, 0.0], <HList![i32, f64] as DefaultHList>::default());
+/// ```
+///
+/// Methods without a receiver, like `Default::default`, are called on each element type.
+/// Outputs mentioning `Self` are computed per element, so such methods have no `_at_index` variant,
+/// but can opt into `_at::<I>()` to be called on one element.
+///
+/// Traits marked with [`macro@trait_hlist_export`] in other crates are lifted the same way,
+/// with `for trait other_crate::Trait use other_crate::__trait_sigs;`.
+///
+/// ## Signature Checks
+///
+/// Each listed signature is checked against the base trait method it calls.
+/// A mismatch, like `&self` listed for a `&mut self` method, is reported once
+/// per method, comparing both signatures and naming the base trait method:
+/// ```rust,compile_fail
+/// use hlist2_trait_macro::TraitHList;
+///
+/// trait Counter {
+///     fn bump(&mut self, by: u8) -> bool;
+/// }
+///
+/// TraitHList! {
+///     CounterHList for trait Counter {
+///         fn bump(&self, by: u8) -> bool; // the signature of `bump` does not match `Counter::bump`
+///     }
+/// }
+/// ```
+///
+/// Methods mentioning `impl Trait`, and `async`, `unsafe` or `extern` methods, call the base trait
+/// method directly, so a mismatch there is a plain type error.
+///
+/// ## Numeric Reductions
+///
+/// Methods can opt into reductions over their outputs with `#[hlist(...)]`.
+/// They fold head to tail without building the intermediate list, adding and multiplying
+/// in the same order as `Iterator::sum` and `Iterator::product`:
+///
+/// | Companion | Output | Requirement |
+/// |-----------|--------|-------------|
+/// | `sum_<m>`         | `T`         | `T: Sum + Add<Output = T>` |
+/// | `product_<m>`     | `T`         | `T: Product + Mul<Output = T>` |
+/// | `checked_sum_<m>` | `Option<T>` | Primitive integer `T`, `None` on overflow |
+/// | `mean_<m>`        | `T`         | `f32` or `f64`, NaN for empty lists, also generates `sum_<m>` |
+///
+/// ```rust
+/// use hlist2::hlist;
+/// use hlist2_trait_macro::TraitHList;
+///
+/// trait Weight {
+///     fn weight(&self) -> u8;
+/// }
+/// impl Weight for u8 {
+///     fn weight(&self) -> u8 { *self }
+/// }
+///
+/// TraitHList! {
+///     WeightHList for trait Weight {
+///         #[hlist(sum, checked_sum)]
+///         fn weight(&self) -> u8;
+///     }
+/// }
+///
+/// assert_eq!(hlist![2u8, 3u8].sum_weight(), 5);
+/// assert_eq!(hlist![200u8, 100u8].checked_sum_weight(), None);
+/// ```
+///
+/// The output type of a reduced method must not depend on `Self`.
+///
+/// ## Ordering Reductions
+///
+/// Methods with `PartialOrd` outputs can opt into `max`, `min`, `argmax`, `argmin` and `is_sorted`:
+///
+/// | Companion | Output |
+/// |-----------|--------|
+/// | `max_<m>`, `min_<m>`       | `Option<T>`, `None` for empty lists |
+/// | `argmax_<m>`, `argmin_<m>` | `Option<usize>`, the first index on ties |
+/// | `is_sorted_<m>`            | `bool`, stops at the first element out of order |
+///
+/// Methods returning `Ordering` always get `cmp_<m>`, comparing lexicographically
+/// and stopping at the first element that is not `Equal`.
+///
+/// ```rust
+/// use hlist2::hlist;
+/// use hlist2_trait_macro::TraitHList;
+///
+/// trait Priority {
+///     fn priority(&self) -> u8;
+/// }
+/// impl Priority for u8 {
+///     fn priority(&self) -> u8 { *self }
+/// }
+///
+/// TraitHList! {
+///     PriorityHList for trait Priority {
+///         #[hlist(max, argmax)]
+///         fn priority(&self) -> u8;
+///     }
+/// }
+///
+/// assert_eq!(hlist![1u8, 7u8, 7u8].max_priority(), Some(7));
+/// assert_eq!(hlist![1u8, 7u8, 7u8].argmax_priority(), Some(1));
+/// ```
+///
+/// ## Fallible Methods
+///
+/// Methods returning `Result<T, E>` gain:
+/// - `.try_<method>()` — `Result` of an hlist of all `T`, stopping at the first `Err`.
+/// - `.collect_errors_<method>()` — evaluates every element and returns a `Vec<(usize, E)>`
+///   of the failing indices and their errors.
+///
+/// Errors can be converted with `From` into a common error type, declared with
+/// `#[hlist(error = Type)]`. In front of the header, it applies to every method returning `Result`.
+/// Without it, `E` must not depend on `Self`.
+///
+/// Methods returning `Option<T>` gain `.all_some_<method>()`, an `Option` of an hlist of all `T`.
+///
+/// ```rust
+/// use hlist2::hlist;
+/// use hlist2_trait_macro::TraitHList;
+///
+/// struct Invalid;
+/// #[derive(Debug, PartialEq)]
+/// struct Error(&'static str);
+/// impl From<Invalid> for Error {
+///     fn from(_: Invalid) -> Self { Error("invalid") }
+/// }
+///
+/// trait Validate {
+///     fn validate(&self) -> Result<u8, Invalid>;
+/// }
+/// impl Validate for u8 {
+///     fn validate(&self) -> Result<u8, Invalid> {
+///         if *self < 100 { Ok(*self) } else { Err(Invalid) }
+///     }
+/// }
+///
+/// TraitHList! {
+///     ValidateHList for trait Validate {
+///         #[hlist(error = Error)]
+///         fn validate(&self) -> Result<u8, Invalid>;
+///     }
+/// }
+///
+/// assert_eq!(hlist![1u8, 2u8].try_validate(), Ok(hlist![1, 2]));
+/// assert_eq!(hlist![1u8, 200u8].try_validate(), Err(Error("invalid")));
+/// assert_eq!(hlist![100u8, 2u8, 200u8].collect_errors_validate().len(), 2);
+/// ```
+///
+/// ## Fold and Scan
+///
+/// Methods whose output does not depend on `Self` gain closure-based companions:
+/// - `.fold_<method>(args.., init, |acc, output| ..)` threads an accumulator through the outputs, head to tail.
+/// - `.scan_<method>(args.., init, |acc, output| ..)` returns an hlist of the accumulator after each element.
+///   The accumulator has to be `Clone`.
+///
+/// ```rust
+/// use hlist2::hlist;
+/// use hlist2_trait_macro::TraitHList;
+///
+/// trait Size {
+///     fn size(&self) -> usize;
+/// }
+/// impl Size for &str {
+///     fn size(&self) -> usize { self.len() }
+/// }
+/// impl Size for u64 {
+///     fn size(&self) -> usize { 8 }
+/// }
+///
+/// TraitHList! {
+///     SizeHList for trait Size {
+///         fn size(&self) -> usize;
+///     }
+/// }
+///
+/// let list = hlist!["four", 0u64, "two"];
+/// assert_eq!(list.fold_size(0, |largest, size| largest.max(size)), 8);
+/// assert_eq!(list.scan_size(0, |total, size| total + size), hlist![4, 12, 15]);
+/// ```
+///
+/// ## Collecting Outputs
+///
+/// The HList trait has a `LEN` constant with the number of elements.
+/// Methods whose output does not depend on `Self` can be collected into ordinary containers:
+/// - `.<method>_vec()` returns a `Vec` of the outputs. Methods opt into it with `#[hlist(vec)]`.
+/// - `.<method>_array()` returns an array of the outputs. Its length is checked against `LEN` at compile time.
+/// - `.<method>_iter()` evaluates one element per pulled item. It is generated when
+///   the method takes `&self` or `&mut self`, or no receiver, and no `&mut` arguments.
+///
+/// ```rust
+/// use hlist2::hlist;
+/// use hlist2_trait_macro::TraitHList;
+///
+/// trait Id {
+///     fn id(&self) -> u32;
+/// }
+/// impl Id for u32 {
+///     fn id(&self) -> u32 { *self }
+/// }
+/// impl Id for char {
+///     fn id(&self) -> u32 { *self as u32 }
+/// }
+///
+/// TraitHList! {
+///     IdHList for trait Id {
+///         #[hlist(vec)]
+///         fn id(&self) -> u32;
+///     }
+/// }
+///
+/// let list = hlist![7u32, 'A'];
+/// assert_eq!(list.id_vec(), vec![7, 65]);
+/// assert_eq!(list.id_array(), [7, 65]);
+/// assert_eq!(list.id_iter().sum::<u32>(), 72);
+/// ```
+///
+/// ```rust,compile_fail
+/// # use hlist2::hlist;
+/// # use hlist2_trait_macro::TraitHList;
+/// # trait Id {
+/// #     fn id(&self) -> u32;
+/// # }
+/// # impl Id for u32 {
+/// #     fn id(&self) -> u32 { *self }
+/// # }
+/// # TraitHList! {
+/// #     IdHList for trait Id {
+/// #         fn id(&self) -> u32;
+/// #     }
+/// # }
+/// let ids: [u32; 3] = hlist![1u32, 2u32].id_array(); // The array length has to match the length of the list.
+/// ```
+///
+/// ## Joining Outputs
+///
+/// Methods returning `String`, `&str`, `Box<str>`, `Rc<str>`, `Arc<str>` or `Cow<str>` gain:
+/// - `.join_<method>(args.., sep)` — a `String` of all outputs separated by `sep`.
+/// - `.write_<method>(args.., writer, sep)` — streams the outputs into a `core::fmt::Write`
+///   without intermediate allocations.
+///
+/// Other outputs implementing `Display` can opt in with `#[hlist(join)]`.
+/// Outputs borrowing from `&self`, like `&str`, can be listed with elided lifetimes.
+///
+/// ```rust
+/// use hlist2::hlist;
+/// use hlist2_trait_macro::TraitHList;
+///
+/// trait Device {
+///     fn label(&self) -> &str;
+///     fn load(&self) -> f32;
+/// }
+/// impl Device for String {
+///     fn label(&self) -> &str { self }
+///     fn load(&self) -> f32 { 0.5 }
+/// }
+/// impl Device for u8 {
+///     fn label(&self) -> &str { "sensor" }
+///     fn load(&self) -> f32 { *self as f32 }
+/// }
+///
+/// TraitHList! {
+///     DeviceHList for trait Device {
+///         fn label(&self) -> &str;
+///         #[hlist(join)]
+///         fn load(&self) -> f32;
+///     }
+/// }
+///
+/// let devices = hlist![String::from("pump"), 2u8];
+/// assert_eq!(devices.join_label(", "), "pump, sensor");
+/// assert_eq!(devices.join_load("/"), "0.5/2");
+/// ```
+///
+/// ## Unzipping Tuples
+///
+/// Methods returning 2- or 3-tuples gain `.unzip_<method>()`, returning a tuple of hlists,
+/// one per tuple position. Their types are the associated types `<METHOD>HListOutput0`,
+/// `<METHOD>HListOutput1` and `<METHOD>HListOutput2`.
+///
+/// ```rust
+/// use hlist2::hlist;
+/// use hlist2_trait_macro::TraitHList;
+///
+/// trait Split {
+///     fn split(&self) -> (u8, char);
+/// }
+/// impl Split for u8 {
+///     fn split(&self) -> (u8, char) { (*self, 'n') }
+/// }
+/// impl Split for char {
+///     fn split(&self) -> (u8, char) { (0, *self) }
+/// }
+///
+/// TraitHList! {
+///     SplitHList for trait Split {
+///         fn split(&self) -> (u8, char);
+///     }
+/// }
+///
+/// assert_eq!(hlist![7u8, 'c'].unzip_split(), (hlist![7, 0], hlist!['n', 'c']));
+/// ```
+///
+/// ## Flattening Outputs
+///
+/// Methods returning `Vec<T>` gain `.flat_<method>()`, a `Vec<T>` of all items in order,
+/// and `.flat_<method>_iter()`, which evaluates one element at a time as items are pulled.
+/// Other `IntoIterator` outputs opt in with `#[hlist(flat)]`.
+///
+/// Methods returning hlists can opt into `.concat_<method>()` with `#[hlist(concat)]`,
+/// which appends the output hlists of all elements into one hlist.
+///
+/// ```rust
+/// use hlist2::{hlist, HList};
+/// use hlist2_trait_macro::TraitHList;
+///
+/// trait Node {
+///     fn children(&self) -> Vec<u32>;
+///     fn ports(&self) -> HList![u16, bool];
+/// }
+/// impl Node for u32 {
+///     fn children(&self) -> Vec<u32> { vec![*self, *self + 1] }
+///     fn ports(&self) -> HList![u16, bool] { hlist![80, true] }
+/// }
+/// impl Node for () {
+///     fn children(&self) -> Vec<u32> { vec![] }
+///     fn ports(&self) -> HList![u16, bool] { hlist![22, false] }
+/// }
+///
+/// TraitHList! {
+///     NodeHList for trait Node {
+///         fn children(&self) -> Vec<u32>;
+///         #[hlist(concat)]
+///         fn ports(&self) -> HList![u16, bool];
+///     }
+/// }
+///
+/// let nodes = hlist![1u32, (), 5u32];
+/// assert_eq!(nodes.flat_children(), vec![1, 2, 5, 6]);
+/// assert_eq!(nodes.flat_children_iter().max(), Some(6));
+/// assert_eq!(nodes.concat_ports(), hlist![80, true, 22, false, 80, true]);
+/// ```
+///
+/// ## Chaining Mutators
+///
+/// Methods without output, taking `&mut self` or `&self`, can return the list itself
+/// instead of an hlist of `()` with `#[hlist(chain)]`. No output type is generated for them.
+/// Methods taking `self` can't be chained, since every element is consumed.
+///
+/// ```rust
+/// use hlist2::hlist;
+/// use hlist2_trait_macro::TraitHList;
+///
+/// trait Clock {
+///     fn reset(&mut self);
+///     fn tick(&mut self, dt: f32);
+///     fn time(&self) -> f32;
+/// }
+/// impl Clock for f32 {
+///     fn reset(&mut self) { *self = 0.0 }
+///     fn tick(&mut self, dt: f32) { *self += dt }
+///     fn time(&self) -> f32 { *self }
+/// }
+///
+/// TraitHList! {
+///     ClockHList for trait Clock {
+///         #[hlist(chain)]
+///         fn reset(&mut self);
+///         #[hlist(chain)]
+///         fn tick(&mut self, dt: f32);
+///         fn time(&self) -> f32;
+///     }
+/// }
+///
+/// let mut clocks = hlist![1.0f32, 2.0f32];
+/// assert_eq!(clocks.reset().tick(0.5).tick(0.5).time(), hlist![1.0, 1.0]);
+/// ```
+///
+/// ## Builder Methods
+///
+/// Methods returning exactly `Self` return the list type itself, rather than an output type,
+/// so builders can be chained on the whole list, also in generic code:
+///
+/// ```rust
+/// use hlist2::hlist;
+/// use hlist2_trait_macro::TraitHList;
+///
+/// trait Gain: Sized {
+///     fn with_gain(self, gain: f32) -> Self;
+/// }
+/// impl Gain for f32 {
+///     fn with_gain(self, gain: f32) -> Self { self * gain }
+/// }
+/// impl Gain for (f32, f32) {
+///     fn with_gain(self, gain: f32) -> Self { (self.0 * gain, self.1 * gain) }
+/// }
+///
+/// TraitHList! {
+///     GainHList for trait Gain {
+///         fn with_gain(self, gain: f32) -> Self;
+///     }
+/// }
+///
+/// fn halve<L: GainHList>(list: L) -> L {
+///     list.with_gain(0.5)
+/// }
+///
+/// assert_eq!(halve(hlist![2.0f32, (4.0f32, 8.0f32)]).with_gain(3.0), hlist![3.0, (6.0, 12.0)]);
+/// ```
+///
+/// ## Selecting Companions
+///
+/// Companions like `_at_index`, `all_` or `count_` are generated whenever a method supports them.
+/// `#[hlist(...)]` on a method chooses among them:
+///
+/// - `companions(at_index, sum, ...)` generates only the listed companions, including opt-in ones like `sum`.
+/// - `no_<companion>`, like `no_at_index`, leaves one out.
+/// - `<companion> = name`, like `all = every_ready`, renames one.
+///
+/// Companions are named after their prefix or suffix: `at_index`, `all`, `vec`, `flat_iter`, and so on.
+/// Companions built on top of one that is left out, like `_iter` on `_at_index`, are left out as well,
+/// unless they are listed explicitly.
+///
+/// `#[hlist(...)]` in front of the header sets defaults for every method, which methods can override:
+///
+/// ```rust
+/// use hlist2::hlist;
+/// use hlist2_trait_macro::TraitHList;
+///
+/// trait Sensor {
+///     fn ready(&self) -> bool;
+///     fn reading(&self) -> u32;
+/// }
+/// impl Sensor for u32 {
+///     fn ready(&self) -> bool { *self > 0 }
+///     fn reading(&self) -> u32 { *self }
+/// }
+///
+/// TraitHList! {
+///     #[hlist(no_at_index)]
+///     SensorHList for trait Sensor {
+///         #[hlist(companions(all, any), all = every_ready)]
+///         fn ready(&self) -> bool;
+///         #[hlist(companions(at_index, sum))]
+///         fn reading(&self) -> u32;
+///     }
+/// }
+///
+/// let sensors = hlist![3u32, 0u32];
+/// assert!(!sensors.every_ready());
+/// assert!(sensors.any_ready());
+/// assert_eq!(sensors.sum_reading(), 3);
+/// assert_eq!(sensors.reading_at_index(1), 0);
+/// ```
+///
+/// ## Index Access
+///
+/// Methods whose output does not depend on `Self` can be called on a single element by its index.
+/// `<method>_at_index(.., index)` panics past the end of the list, naming the method and the length of the list,
+/// while `get_<method>(.., index)`, opt-in with `#[hlist(get)]`, returns `None`.
+/// Both compare the index against a constant per element, which the compiler turns into a jump table,
+/// so the last element is reached as fast as the first:
+///
+/// ```rust
+/// use hlist2::hlist;
+/// use hlist2_trait_macro::TraitHList;
+///
+/// trait Label {
+///     fn label(&self) -> String;
+/// }
+/// impl Label for u8 {
+///     fn label(&self) -> String { format!("u8 {self}") }
+/// }
+/// impl Label for char {
+///     fn label(&self) -> String { format!("char {self}") }
+/// }
+///
+/// TraitHList! {
+///     LabelHList for trait Label {
+///         #[hlist(get)]
+///         fn label(&self) -> String;
+///     }
+/// }
+///
+/// let widgets = hlist![1u8, 'x'];
+/// assert_eq!(widgets.label_at_index(0), "u8 1");
+/// assert_eq!(widgets.get_label(1), Some(String::from("char x")));
+/// assert_eq!(widgets.get_label(2), None);
+/// ```
+///
+/// `<method>_at_indices(.., indices)` calls the method for every index of an iterator, in order,
+/// and collects the outputs into a `Vec` unless they are `()`:
+///
+/// ```rust
+/// # use hlist2::hlist;
+/// # use hlist2_trait_macro::TraitHList;
+/// # trait Label {
+/// #     fn label(&self) -> String;
+/// # }
+/// # impl Label for u8 {
+/// #     fn label(&self) -> String { format!("u8 {self}") }
+/// # }
+/// # TraitHList! {
+/// #     LabelHList for trait Label {
+/// #         fn label(&self) -> String;
+/// #     }
+/// # }
+/// let widgets = hlist![1u8, 2u8, 3u8];
+/// assert_eq!(widgets.label_at_indices([2, 0]), vec!["u8 3", "u8 1"]);
+/// ```
+///
+/// ## Typed Index Access
+///
+/// Methods can opt into `<method>_at::<I>()` with `#[hlist(at)]`, which calls the method on a single element
+/// picked by a type-level index of `hlist2`, `Here` for the head and `There<I>` for the element after `I`.
+/// It returns the output of that element, so it works for outputs depending on `Self` as well:
+///
+/// ```rust
+/// use hlist2::{HList, hlist, ops::{Here, There}};
+/// use hlist2_trait_macro::TraitHList;
+///
+/// TraitHList! {
+///     CloneHList for trait Clone {
+///         #[hlist(at)]
+///         fn clone(&self) -> Self;
+///     }
+/// }
+/// TraitHList! {
+///     DefaultHList for trait Default {
+///         #[hlist(at)]
+///         fn default() -> Self;
+///     }
+/// }
+///
+/// let list = hlist![1u8, String::from("two")];
+/// let two: String = list.clone_at::<There<Here>>();
+/// assert_eq!(two, "two");
+/// assert_eq!(<HList![u8, String] as DefaultHList>::default_at::<Here>(), 0);
+/// ```
+///
+/// Indices past the end of the list are rejected at compile time:
+///
+/// ```rust,compile_fail
+/// use hlist2::{hlist, ops::{Here, There}};
+/// use hlist2_trait_macro::TraitHList;
+///
+/// TraitHList! {
+///     CloneHList for trait Clone {
+///         #[hlist(at)]
+///         fn clone(&self) -> Self;
+///     }
+/// }
+///
+/// hlist![1u8, 2u16].clone_at::<There<There<Here>>>();
+/// ```
+///
+/// ## Reverse Evaluation
+///
+/// Methods can opt into `rev_<method>()` with `#[hlist(rev)]`, which calls the elements from the last
+/// to the first, for teardown sequences or LIFO middleware. Outputs stay in list order.
+/// For `bool` outputs, `rev_all_<method>()` and `rev_any_<method>()` short-circuit from the last element:
+///
+/// ```rust
+/// use hlist2::hlist;
+/// use hlist2_trait_macro::TraitHList;
+///
+/// trait Service {
+///     fn shutdown(&mut self, log: &mut Vec<&'static str>) -> bool;
+/// }
+/// struct Named(&'static str);
+/// impl Service for Named {
+///     fn shutdown(&mut self, log: &mut Vec<&'static str>) -> bool {
+///         log.push(self.0);
+///         self.0 != "cache"
+///     }
+/// }
+///
+/// TraitHList! {
+///     ServiceHList for trait Service {
+///         #[hlist(rev)]
+///         fn shutdown(&mut self, log: &mut Vec<&'static str>) -> bool;
+///     }
+/// }
+///
+/// let mut services = hlist![Named("auth"), Named("cache"), Named("db")];
+/// let mut log = vec![];
+/// assert_eq!(services.rev_shutdown(&mut log), hlist![true, false, true]);
+/// assert_eq!(log, ["db", "cache", "auth"]);
+///
+/// log.clear();
+/// assert!(!services.rev_all_shutdown(&mut log));
+/// assert_eq!(log, ["db", "cache"]);
+/// ```
+///
+/// ## Enumerated Arguments
+///
+/// An argument marked `#[hlist(enumerate)]` receives the position of each element in the list.
+/// The list-level method leaves that argument out and counts from `0`, so no counter has to be
+/// threaded through the calls by hand. Methods with an enumerated argument get no companion methods:
+///
+/// ```rust
+/// use hlist2::hlist;
+/// use hlist2_trait_macro::TraitHList;
+///
+/// trait Widget {
+///     fn render(&self, slot: usize, out: &mut Vec<String>);
+/// }
+/// struct Label(&'static str);
+/// impl Widget for Label {
+///     fn render(&self, slot: usize, out: &mut Vec<String>) {
+///         out.push(format!("{slot}: {}", self.0));
+///     }
+/// }
+///
+/// TraitHList! {
+///     WidgetHList for trait Widget {
+///         fn render(&self, #[hlist(enumerate)] slot: usize, out: &mut Vec<String>);
+///     }
+/// }
+///
+/// let widgets = hlist![Label("title"), Label("body")];
+/// let mut out = vec![];
+/// widgets.render(&mut out);
+/// assert_eq!(out, ["0: title", "1: body"]);
+/// ```
+///
+/// ## Zipped Arguments
+///
+/// An argument marked `#[hlist(zip)]` takes an hlist with one value per element instead of a single
+/// value that is cloned for every element, so each element gets its own value, even a `&mut`
+/// borrow. The list-level method takes the hlist in its place, and lists of another length don't
+/// compile. As with enumerated arguments, no companion methods are generated:
+///
+/// ```rust
+/// use hlist2::hlist;
+/// use hlist2_trait_macro::TraitHList;
+///
+/// trait Channel {
+///     fn mix(&self, gain: f32, out: &mut Vec<f32>);
+/// }
+/// struct Tone(f32);
+/// impl Channel for Tone {
+///     fn mix(&self, gain: f32, out: &mut Vec<f32>) {
+///         out.push(self.0 * gain);
+///     }
+/// }
+///
+/// TraitHList! {
+///     ChannelHList for trait Channel {
+///         fn mix(&self, #[hlist(zip)] gain: f32, out: &mut Vec<f32>);
+///     }
+/// }
+///
+/// let channels = hlist![Tone(1.0), Tone(2.0)];
+/// let mut out = vec![];
+/// channels.mix(hlist![0.5, 2.0], &mut out);
+/// assert_eq!(out, [0.5, 4.0]);
+/// ```
+///
+/// ```rust,compile_fail
+/// # use hlist2::hlist;
+/// # use hlist2_trait_macro::TraitHList;
+/// # trait Channel {
+/// #     fn mix(&self, gain: f32, out: &mut Vec<f32>);
+/// # }
+/// # struct Tone(f32);
+/// # impl Channel for Tone {
+/// #     fn mix(&self, gain: f32, out: &mut Vec<f32>) {
+/// #         out.push(self.0 * gain);
+/// #     }
+/// # }
+/// # TraitHList! {
+/// #     ChannelHList for trait Channel {
+/// #         fn mix(&self, #[hlist(zip)] gain: f32, out: &mut Vec<f32>);
+/// #     }
+/// # }
+/// let channels = hlist![Tone(1.0), Tone(2.0)];
+/// channels.mix(hlist![0.5], &mut vec![]); // one gain for two channels
+/// ```
+///
 /// ## Comments and Unused Methods
 ///
 /// Any methods omitted in the macro definition are ignored.  
@@ -275,10 +1071,30 @@ use angle_bracketed_generic_params::AngleBracketedGenericParams;
 /// | Arbitrary trait-level generics and bounds | ✅ | Generic, const, lifetime parameters |
 /// | Trait-level `where` clauses        | ✅ | Fully supported |
 /// | Arbitrary method-level generics and bounds | ⚠️ | Generic lifetimes introduce additional explicit lifetime bounds |
+/// | Elided output lifetimes            | ✅ | Borrowing from `&self` or `&mut self` |
 /// | Method-level `where` clauses             | ✅ | Fully supported  |
 /// | Different receiver forms           | ✅ | `self`, `&self`, `&mut self` |
 /// | Method renaming                    | ✅ | `#[name = ...]` attribute |
-/// | Additional convenience methods     | ✅ | `any_*`, `all_*` for `bool`-returning methods |
+/// | Listed signatures checked against the base trait | ✅ | Plain type errors for `impl Trait` arguments |
+/// | Known signatures of `std` traits    | ✅ | `TraitHList!{ CloneHList for trait Clone; }` |
+/// | Signatures exported by other crates | ✅ | `#[trait_hlist_export]` and `use <crate>::__<trait>_sigs` |
+/// | Additional convenience methods     | ✅ | `any_*`, `all_*`, `none_*`, `count_*`, `exactly_one_*`, `mask_*`, `position_*`, `rposition_*` for `bool`-returning methods, `find_map_*` for `Option` |
+/// | Numeric reductions                 | ✅ | Opt-in `sum_*`, `product_*`, `checked_sum_*`, `mean_*` |
+/// | Ordering reductions                | ✅ | Opt-in `max_*`, `min_*`, `argmax_*`, `argmin_*`, `is_sorted_*`, `cmp_*` for `Ordering` |
+/// | Fallible methods                   | ✅ | `try_*`, `collect_errors_*` for `Result`, `all_some_*` for `Option` |
+/// | Fold and scan with a closure       | ✅ | `fold_*`, `scan_*` for outputs not depending on `Self` |
+/// | Collecting uniform outputs         | ✅ | `LEN`, `*_array`, `*_iter`, opt-in `*_vec` |
+/// | Joining outputs with a separator   | ✅ | `join_*`, `write_*` for string outputs, or `#[hlist(join)]` |
+/// | Unzipping tuple outputs            | ✅ | `unzip_*` for 2- and 3-tuples |
+/// | Flattening outputs                 | ✅ | `flat_*`, `flat_*_iter` for `Vec` or `#[hlist(flat)]`, `#[hlist(concat)]` for hlists |
+/// | Chaining unit-returning methods    | ✅ | `#[hlist(chain)]` returns `&mut Self` or `&Self` |
+/// | Builder methods returning `Self`   | ✅ | The list-level method returns the list type |
+/// | Selecting and renaming companions  | ✅ | `#[hlist(companions(...))]`, `#[hlist(no_*)]`, `#[hlist(<companion> = name)]`, defaults and naming templates in the header |
+/// | Index access                       | ✅ | `*_at_index` panics out of bounds, opt-in `get_*` returns `Option`, `*_at_indices` for many indices |
+/// | Typed index access                 | ✅ | Opt-in `*_at::<I>()` with `hlist2` index types, checked at compile time |
+/// | Reverse evaluation                 | ✅ | `rev_*`, `rev_all_*`, `rev_any_*` with `#[hlist(rev)]` |
+/// | Enumerated arguments               | ✅ | `#[hlist(enumerate)]` passes each element its position |
+/// | Zipped arguments                   | ✅ | `#[hlist(zip)]` takes an hlist with one value per element |
 /// | Comments in macro body             | ✅ | Ignored |
 /// | Associated types in traits | ⛔ | Not planned until usecase if found |
 #[allow(non_snake_case)]
@@ -287,3 +1103,39 @@ pub fn TraitHList(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = syn::parse_macro_input!(input as TraitHListInput);
     proc_macro::TokenStream::from(input.expand())
 }
+
+/// Attribute, that exports method signatures of a trait for [`TraitHList!`] invocations
+/// in other crates.
+///
+/// The trait is emitted unchanged, together with a hidden exported macro
+/// `__<trait_name>_sigs` (the name can be given explicitly as `#[trait_hlist_export(name)]`).
+/// Downstream crates refer to that macro with `use` instead of restating the signatures,
+/// so changes of the trait propagate to them automatically:
+///
+/// ```rust,ignore
+/// // core_crate
+/// #[hlist2_trait_macro::trait_hlist_export]
+/// pub trait Component {
+///     fn update(&mut self, dt: f32);
+///     fn is_alive(&self) -> bool;
+/// }
+///
+/// // downstream crate
+/// hlist2_trait_macro::TraitHList! {
+///     pub ComponentHList for trait core_crate::Component use core_crate::__component_sigs;
+/// }
+/// ```
+///
+/// Like for standard traits, a body with `fn <name>;` items lifts only some methods
+/// and allows renaming them. Trait generics are taken from the exported trait unless given.
+///
+/// Types in the signatures are resolved in the downstream crate, except for paths starting with `crate`.
+#[proc_macro_attribute]
+pub fn trait_hlist_export(
+    attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let macro_ident = syn::parse_macro_input!(attr as Option<syn::Ident>);
+    let item = syn::parse_macro_input!(item as syn::ItemTrait);
+    proc_macro::TokenStream::from(trait_hlist_export::TraitHListExport::new(macro_ident, item).expand())
+}
@@ -0,0 +1,198 @@
+//! `#[hlist(...)]` options on methods and on the macro header.
+
+/// Every companion that can be selected, renamed or excluded.
+const COMPANIONS: &[&str] = &[
+    "at", "at_index", "at_indices", "get", "all", "any", "count", "none", "exactly_one", "mask", "position", "rposition",
+    "find_map", "sum", "product", "checked_sum", "mean", "max", "min", "argmax", "argmin",
+    "is_sorted", "cmp", "fold", "scan", "vec", "array", "iter", "join", "write", "unzip", "flat",
+    "flat_iter", "concat", "try", "collect_errors", "all_some", "rev", "rev_all", "rev_any",
+];
+
+/// Companions that are only generated when requested.
+const OPT_IN_COMPANIONS: &[&str] = &[
+    "at", "get", "vec", "sum", "product", "checked_sum", "mean", "max", "min", "argmax", "argmin", "is_sorted", "join",
+    "flat", "concat", "rev", "rev_all", "rev_any",
+];
+
+#[derive(Clone, Default)]
+pub struct HListOptions {
+    /// Set by `companions(...)`: no other companion is generated.
+    only: Option<Vec<String>>,
+    /// Opt-in companions, and companions listed or renamed on the method.
+    requested: Vec<String>,
+    /// Set by `no_<companion>`.
+    excluded: Vec<String>,
+    /// Set by `<companion> = name` or `<companion> = "template"`.
+    names: Vec<(String, Name)>,
+    /// Set by `rename = "template"`.
+    rename: Option<syn::LitStr>,
+    /// Set by `error = Type`.
+    pub error_type: Option<syn::Type>,
+    /// Set by `error = Type` in the header, only used by methods returning `Result`.
+    pub default_error_type: Option<syn::Type>,
+    /// Set by `chain`.
+    pub chain: bool,
+}
+
+impl HListOptions {
+    /// Parses one `#[hlist(...)]` attribute, either on a method or on the macro header.
+    pub fn parse(&mut self, list: &syn::MetaList, header: bool) -> syn::Result<()> {
+        list.parse_nested_meta(|meta| {
+            let Some(ident) = meta.path.get_ident() else {
+                return Err(meta.error("Expected a companion method name."));
+            };
+            let key = ident.to_string();
+            let only_on_methods = || Err(meta.error(format!("`{key}` is only supported on methods.")));
+
+            if key == "chain" {
+                if header {
+                    return only_on_methods();
+                }
+                self.chain = true;
+            } else if key == "rename" {
+                self.rename = Some(template(meta.value()?.parse()?)?);
+            } else if key == "error" {
+                self.error_type = Some(meta.value()?.parse()?);
+            } else if key == "companions" {
+                let mut only = vec![];
+                meta.parse_nested_meta(|meta| {
+                    only.push(companion_key(&meta, meta.path.get_ident())?);
+                    Ok(())
+                })?;
+                if !header {
+                    self.requested.extend(only.iter().cloned());
+                }
+                self.only = Some(only);
+            } else if let Some(excluded) = key.strip_prefix("no_").filter(|key| COMPANIONS.contains(key)) {
+                self.excluded.push(excluded.to_string());
+            } else if meta.input.peek(syn::Token![=]) {
+                let key = companion_key(&meta, Some(ident))?;
+                let value = meta.value()?;
+                let name = if value.peek(syn::LitStr) {
+                    Name::Template(template(value.parse()?)?)
+                } else if header {
+                    return Err(meta.error("Names in the header are templates like \"{}_at\", since they apply to every method."));
+                } else {
+                    Name::Ident(value.parse()?)
+                };
+                self.names.push((key.clone(), name));
+                if !header {
+                    self.requested.push(key);
+                }
+            } else {
+                if !OPT_IN_COMPANIONS.contains(&key.as_str()) {
+                    let opt_in = OPT_IN_COMPANIONS.join(", ");
+                    return Err(meta.error(if COMPANIONS.contains(&key.as_str()) {
+                        format!(
+                            "`{key}` is generated by default, so it can't be listed alone. \
+                             Use `companions({key}, ...)` to keep only some companions. Opt-in companions are: {opt_in}."
+                        )
+                    } else {
+                        format!("Unknown companion method `{key}`, expected one of: {opt_in}.")
+                    }));
+                }
+                if header {
+                    return only_on_methods();
+                }
+                self.requested.push(key);
+            }
+            Ok(())
+        })
+    }
+
+    /// Fills in what the method leaves open from the options of the macro header.
+    pub fn with_defaults(mut self, defaults: &Self) -> Self {
+        let excluded = defaults.excluded.iter().filter(|key| !self.requested.contains(key)).cloned();
+        self.excluded.splice(0..0, excluded.collect::<Vec<_>>());
+        if self.only.is_none() {
+            self.only = defaults.only.clone();
+        }
+        if self.rename.is_none() {
+            self.rename = defaults.rename.clone();
+        }
+        // Names given on the method come first.
+        self.names.extend(defaults.names.iter().cloned());
+        self.default_error_type = defaults.error_type.clone();
+        self
+    }
+
+    /// Whether an opt-in companion was asked for.
+    pub fn requested(&self, key: &str) -> bool {
+        self.requested.iter().any(|requested| requested == key)
+    }
+
+    /// Whether any companion was asked for on the method.
+    pub fn requests_any(&self) -> bool {
+        !self.requested.is_empty()
+    }
+
+    /// Whether a companion may be generated.
+    pub fn allows(&self, key: &str) -> bool {
+        !self.excluded.iter().any(|excluded| excluded == key)
+            && (self.only.as_ref().is_none_or(|only| only.iter().any(|only| only == key)) || self.requested(key))
+    }
+
+    /// The name given to a companion of `method` with `<companion> = name`.
+    pub fn name(&self, key: &str, method: &syn::Ident) -> Option<syn::Ident> {
+        let (_, name) = self.names.iter().find(|(named, _)| named == key)?;
+        Some(match name {
+            Name::Ident(ident) => ident.clone(),
+            Name::Template(template) => apply(template, method),
+        })
+    }
+
+    /// The name of the elementwise method given with `rename = "template"`.
+    pub fn rename(&self, method: &syn::Ident) -> Option<syn::Ident> {
+        self.rename.as_ref().map(|template| apply(template, method))
+    }
+
+    /// Keeps the companions allowed by these options, together with the helpers they call.
+    ///
+    /// Companions calling a companion that is left out are left out as well,
+    /// unless they were asked for on the method, which brings back what they call.
+    pub fn select(&self, companions: Vec<crate::companion::Companion>) -> Vec<crate::companion::Companion> {
+        let calls = |caller: usize, callee: usize| caller != callee && companions[caller].mentions(&companions[callee].ident.to_string());
+        let mut kept: Vec<bool> = companions.iter().map(|companion| !companion.hidden && self.allows(&companion.key)).collect();
+        let all = 0..companions.len();
+        while let Some(caller) = all.clone().find(|&caller| {
+            kept[caller]
+                && !self.requested(&companions[caller].key)
+                && all.clone().any(|callee| !kept[callee] && !companions[callee].hidden && calls(caller, callee))
+        }) {
+            kept[caller] = false;
+        }
+        while let Some(callee) = all.clone().find(|&callee| !kept[callee] && all.clone().any(|caller| kept[caller] && calls(caller, callee))) {
+            kept[callee] = true;
+        }
+        companions.into_iter().zip(kept).filter_map(|(companion, kept)| kept.then_some(companion)).collect()
+    }
+}
+
+#[derive(Clone)]
+enum Name {
+    Ident(syn::Ident),
+    /// `{}` stands for the name of the method.
+    Template(syn::LitStr),
+}
+
+/// Checks that a template names a valid identifier for any method name.
+fn template(template: syn::LitStr) -> syn::Result<syn::LitStr> {
+    let value = template.value();
+    if !value.contains("{}") {
+        return Err(syn::Error::new(template.span(), "Naming templates must contain `{}`, replaced by the method name."));
+    }
+    syn::parse_str::<syn::Ident>(&value.replace("{}", "method"))
+        .map_err(|_| syn::Error::new(template.span(), format!("`{value}` does not make a valid identifier.")))?;
+    Ok(template)
+}
+
+fn apply(template: &syn::LitStr, method: &syn::Ident) -> syn::Ident {
+    syn::Ident::new(&template.value().replace("{}", &method.to_string()), template.span())
+}
+
+fn companion_key(meta: &syn::meta::ParseNestedMeta, ident: Option<&syn::Ident>) -> syn::Result<String> {
+    match ident {
+        Some(ident) if COMPANIONS.iter().any(|key| ident == key) => Ok(ident.to_string()),
+        _ => Err(meta.error(format!("Unknown companion method, expected one of: {}.", COMPANIONS.join(", ")))),
+    }
+}
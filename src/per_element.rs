@@ -0,0 +1,154 @@
+//! Arguments with their own value for every element: `#[hlist(enumerate)]` receives the position
+//! of the element, and `#[hlist(zip)]` the value at the same position in another hlist.
+
+use crate::companion::{Companion, CompanionBody, CompanionContext};
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Marker {
+    Enumerate,
+    Zip,
+}
+
+/// Removes `#[hlist(enumerate)]` and `#[hlist(zip)]` from the arguments of the listed signature,
+/// and returns the arguments they marked.
+pub fn take_markers(sig: &mut syn::Signature) -> Vec<(syn::Ident, Marker)> {
+    let method = sig.ident.clone();
+    let mut markers = vec![];
+    for input in sig.inputs.iter_mut() {
+        let syn::FnArg::Typed(syn::PatType { attrs, pat, .. }) = input else {
+            continue;
+        };
+        for attr in std::mem::take(attrs) {
+            let marker = match &attr.meta {
+                syn::Meta::List(list) if list.path.is_ident("hlist") => match list.parse_args::<syn::Ident>() {
+                    Ok(ident) if ident == "enumerate" => Some(Marker::Enumerate),
+                    Ok(ident) if ident == "zip" => Some(Marker::Zip),
+                    _ => None,
+                },
+                _ => None,
+            };
+            let Some(marker) = marker else {
+                panic!("Unsupported argument attribute or format. Try #[hlist(enumerate)] or #[hlist(zip)].")
+            };
+            let syn::Pat::Ident(syn::PatIdent { ident, .. }) = &**pat else {
+                panic!("Unsupported argument pattern in function '{method}': only simple identifiers are supported")
+            };
+            assert!(
+                markers.iter().all(|(marked, _)| marked != ident),
+                "Argument `{ident}` of `{method}` can only have one `#[hlist(...)]` marker."
+            );
+            assert!(
+                marker == Marker::Zip || markers.iter().all(|(_, marker)| *marker != Marker::Enumerate),
+                "Only one argument of `{method}` can be `#[hlist(enumerate)]`."
+            );
+            markers.push((ident.clone(), marker));
+        }
+    }
+    markers
+}
+
+/// The hidden `__per_element_` variant of the elementwise method, which keeps every marked argument
+/// and gives the head its own value: enumerated arguments are passed on to the tail increased by one,
+/// and zipped arguments are split into the first value and the values for the tail.
+///
+/// Zipped arguments take an hlist of the argument type as long as the list, the associated type
+/// `{METHOD}_{ARG}HListInput`, so that lists of another length don't compile. `hlist_fn_sig` is turned
+/// into the elementwise method, which leaves out enumerated arguments. Its body, starting the variant
+/// at `0`, is returned together with the variant.
+pub fn per_element(
+    ctx: &CompanionContext,
+    hlist_fn_sig: &mut syn::Signature,
+    chain: bool,
+    markers: &[(syn::Ident, Marker)],
+    head_fn: &proc_macro2::TokenStream,
+    head_self: &proc_macro2::TokenStream,
+) -> (Companion, proc_macro2::TokenStream) {
+    let CompanionContext { tail, this, args, args_cloned, generic_args, .. } = ctx;
+    let per_element = ctx.ident("__per_element_");
+    let marker = |arg: &proc_macro2::TokenStream| markers.iter().find(|(ident, _)| *ident == arg.to_string());
+
+    let (mut split, mut head_args, mut tail_args, mut start_args) = (vec![], vec![], vec![], vec![]);
+    for (arg, arg_cloned) in args.iter().zip(args_cloned.iter()) {
+        match marker(arg) {
+            Some((slot, Marker::Enumerate)) => {
+                head_args.push(quote::quote!(#slot));
+                tail_args.push(quote::quote!(#slot + 1));
+                start_args.push(quote::quote!(0));
+            }
+            Some((zipped, Marker::Zip)) => {
+                let rest = crate::hygiene::zip_tail(zipped);
+                split.push(quote::quote!(let hlist2::Cons(#zipped, #rest) = #zipped;));
+                head_args.push(quote::quote!(#zipped));
+                tail_args.push(quote::quote!(#rest));
+                start_args.push(arg.clone());
+            }
+            None => {
+                head_args.push(arg_cloned.clone());
+                tail_args.push(arg.clone());
+                start_args.push(arg.clone());
+            }
+        }
+    }
+
+    let head = quote::quote!(#head_fn(#head_self #(#head_args),*));
+    let body = if chain {
+        CompanionBody::Recursive {
+            nil: quote::quote!(self),
+            cons: quote::quote! {
+                #(#split)*
+                #head;
+                #tail #per_element(#(#tail_args),*);
+                self
+            },
+        }
+    } else {
+        CompanionBody::Recursive {
+            nil: quote::quote!(hlist2::Nil),
+            cons: quote::quote! {
+                #(#split)*
+                hlist2::Cons(#head, #tail #per_element(#(#tail_args),*))
+            },
+        }
+    };
+    let mut companion = ctx.companion("__per_element_", body).with_output(hlist_fn_sig.output.clone());
+
+    let method = ctx.sig.ident.to_string().to_uppercase();
+    let head_ty = crate::hygiene::head_ty();
+    let is_arg = |pat: &syn::Pat, arg: &syn::Ident| matches!(pat, syn::Pat::Ident(ident) if ident.ident == *arg);
+    for (zipped, _) in markers.iter().filter(|(_, marker)| *marker == Marker::Zip) {
+        let ident = quote::format_ident!("{}_{}HListInput", method, zipped.to_string().to_uppercase());
+        let arg_ty = ctx
+            .sig
+            .inputs
+            .iter()
+            .find_map(|input| match input {
+                syn::FnArg::Typed(syn::PatType { pat, ty, .. }) if is_arg(pat, zipped) => Some(ty),
+                _ => None,
+            })
+            .expect("Marked arguments are in the listed signature.");
+        let cons = crate::replace_self::replace_self(quote::ToTokens::to_token_stream(arg_ty), &head_ty);
+        companion.assoc_types.push(ctx.assoc_type_named(ident.clone(), &syn::parse_quote!(#cons), &[]));
+
+        let zipped_ty: syn::Type = syn::parse_quote!(Self::#ident<#(#generic_args),*>);
+        for sig in [&mut companion.sig, &mut *hlist_fn_sig] {
+            for input in sig.inputs.iter_mut() {
+                if let syn::FnArg::Typed(syn::PatType { pat, ty, .. }) = input
+                    && is_arg(pat, zipped)
+                {
+                    **ty = zipped_ty.clone();
+                }
+            }
+        }
+    }
+
+    hlist_fn_sig.inputs = std::mem::take(&mut hlist_fn_sig.inputs)
+        .into_iter()
+        .filter(|input| {
+            !matches!(input, syn::FnArg::Typed(syn::PatType { pat, .. })
+                if markers.iter().any(|(slot, marker)| *marker == Marker::Enumerate && is_arg(pat, slot)))
+        })
+        .collect();
+    hlist_fn_sig.generics.make_where_clause().predicates.push(syn::parse_quote!(Self: Sized));
+
+    (companion, quote::quote!(#this #per_element(#(#start_args),*)))
+}
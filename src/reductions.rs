@@ -0,0 +1,368 @@
+//! Companions folding the outputs of all elements into a single value.
+
+use crate::companion::{Companion, CompanionBody, CompanionContext};
+
+const INTEGER_TYPES: &[&str] = &[
+    "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize",
+];
+
+/// `sum_`, `product_`, `checked_sum_` and `mean_`.
+///
+/// The outputs are accumulated head to tail, like `Iterator::sum` and `Iterator::product`,
+/// through hidden variants taking the accumulator.
+pub fn numeric(ctx: &CompanionContext, requested: impl Fn(&str) -> bool) -> Vec<Companion> {
+    let CompanionContext { output, head, tail, this, args, .. } = ctx;
+    let sum = ctx.ident("sum_");
+    let (sum_from, product_from, checked_sum_from) =
+        (ctx.ident("__sum_"), ctx.ident("__product_"), ctx.ident("__checked_sum_"));
+    let (acc, value) = (crate::hygiene::acc(), crate::hygiene::value());
+    let mut companions = vec![];
+
+    if requested("sum") || requested("mean") {
+        ctx.require_uniform_output("sum");
+        let bounds = ctx.output_bounds(quote::quote!(core::iter::Sum + core::ops::Add<Output = #output>));
+        companions.push(
+            ctx.companion(
+                "__sum_",
+                CompanionBody::Recursive {
+                    nil: quote::quote!(#acc),
+                    cons: quote::quote! {
+                        let #value = #head;
+                        #tail #sum_from(#(#args,)* #acc + #value)
+                    },
+                },
+            )
+            .with_input(syn::parse_quote!(#acc: #output))
+            .with_where_predicates(bounds.clone()),
+        );
+        companions.push(
+            ctx.companion(
+                "sum_",
+                CompanionBody::Provided(quote::quote! {
+                    #this #sum_from(#(#args,)* core::iter::Sum::sum(core::iter::empty::<#output>()))
+                }),
+            )
+            .with_where_predicates(bounds),
+        );
+    }
+    if requested("product") {
+        ctx.require_uniform_output("product");
+        let bounds = ctx.output_bounds(quote::quote!(core::iter::Product + core::ops::Mul<Output = #output>));
+        companions.push(
+            ctx.companion(
+                "__product_",
+                CompanionBody::Recursive {
+                    nil: quote::quote!(#acc),
+                    cons: quote::quote! {
+                        let #value = #head;
+                        #tail #product_from(#(#args,)* #acc * #value)
+                    },
+                },
+            )
+            .with_input(syn::parse_quote!(#acc: #output))
+            .with_where_predicates(bounds.clone()),
+        );
+        companions.push(
+            ctx.companion(
+                "product_",
+                CompanionBody::Provided(quote::quote! {
+                    #this #product_from(#(#args,)* core::iter::Product::product(core::iter::empty::<#output>()))
+                }),
+            )
+            .with_where_predicates(bounds),
+        );
+    }
+    if requested("checked_sum") {
+        assert!(
+            is_primitive(output, INTEGER_TYPES),
+            "`checked_sum` of `{}` requires a primitive integer output type.",
+            ctx.sig.ident
+        );
+        let option: syn::ReturnType = syn::parse_quote!(-> core::option::Option<#output>);
+        companions.push(
+            ctx.companion(
+                "__checked_sum_",
+                CompanionBody::Recursive {
+                    nil: quote::quote!(core::option::Option::Some(#acc)),
+                    cons: quote::quote! {
+                        let #value = #head;
+                        #tail #checked_sum_from(#(#args,)* #acc.checked_add(#value)?)
+                    },
+                },
+            )
+            .with_input(syn::parse_quote!(#acc: #output))
+            .with_output(option.clone()),
+        );
+        companions.push(
+            ctx.companion(
+                "checked_sum_",
+                CompanionBody::Provided(quote::quote!(#this #checked_sum_from(#(#args,)* 0))),
+            )
+            .with_output(option),
+        );
+    }
+    if requested("mean") {
+        assert!(
+            is_primitive(output, &["f32", "f64"]),
+            "`mean` of `{}` requires an `f32` or `f64` output type.",
+            ctx.sig.ident
+        );
+        companions.push(
+            ctx.companion(
+                "mean_",
+                CompanionBody::Provided(quote::quote! {
+                    #this #sum(#(#args),*) / (Self::LEN as #output)
+                }),
+            ),
+        );
+    }
+    companions
+}
+
+/// `max_`, `min_`, `argmax_`, `argmin_` and `is_sorted_`, as well as `cmp_` for `Ordering` outputs.
+///
+/// Ties are resolved towards the first element.
+pub fn ordering(ctx: &CompanionContext, requested: impl Fn(&str) -> bool) -> Vec<Companion> {
+    let CompanionContext { output, head, tail, this, args, .. } = ctx;
+    let (index, value, other) = (crate::hygiene::index(), crate::hygiene::value(), crate::hygiene::other());
+    let partial_ord = || ctx.output_bounds(quote::quote!(core::cmp::PartialOrd));
+    let mut companions = vec![];
+
+    for (extreme, arg_extreme, helper_prefix, wins) in [
+        ("max", "argmax", "__argmax_", quote::quote!(>)),
+        ("min", "argmin", "__argmin_", quote::quote!(<)),
+    ] {
+        if !requested(extreme) && !requested(arg_extreme) {
+            continue;
+        }
+        ctx.require_uniform_output(extreme);
+        let helper = ctx.ident(helper_prefix);
+        companions.push(
+            ctx.companion(
+                helper_prefix,
+                CompanionBody::Recursive {
+                    nil: quote::quote!(core::option::Option::None),
+                    cons: quote::quote! {
+                        let #value = #head;
+                        match #tail #helper(#(#args),*) {
+                            core::option::Option::Some((#index, #other)) if #other #wins #value => {
+                                core::option::Option::Some((#index + 1, #other))
+                            }
+                            _ => core::option::Option::Some((0, #value)),
+                        }
+                    },
+                },
+            )
+            .with_output(syn::parse_quote!(-> core::option::Option<(usize, #output)>))
+            .with_where_predicates(partial_ord()),
+        );
+        if requested(extreme) {
+            companions.push(
+                ctx.companion(
+                    &format!("{extreme}_"),
+                    CompanionBody::Provided(quote::quote! {
+                        core::option::Option::map(#this #helper(#(#args),*), |(_, #value)| #value)
+                    }),
+                )
+                .with_output(syn::parse_quote!(-> core::option::Option<#output>))
+                .with_where_predicates(partial_ord()),
+            );
+        }
+        if requested(arg_extreme) {
+            companions.push(
+                ctx.companion(
+                    &format!("{arg_extreme}_"),
+                    CompanionBody::Provided(quote::quote! {
+                        core::option::Option::map(#this #helper(#(#args),*), |(#index, _)| #index)
+                    }),
+                )
+                .with_output(syn::parse_quote!(-> core::option::Option<usize>))
+                .with_where_predicates(partial_ord()),
+            );
+        }
+    }
+
+    if requested("is_sorted") {
+        ctx.require_uniform_output("is_sorted");
+        let after = ctx.ident("__is_sorted_after_");
+        companions.push(
+            ctx.companion(
+                "is_sorted_",
+                CompanionBody::Recursive {
+                    nil: quote::quote!(true),
+                    cons: quote::quote! {
+                        let #value = #head;
+                        #tail #after(#(#args,)* &#value)
+                    },
+                },
+            )
+            .with_output(syn::parse_quote!(-> bool))
+            .with_where_predicates(partial_ord()),
+        );
+        companions.push(
+            ctx.companion(
+                "__is_sorted_after_",
+                CompanionBody::Recursive {
+                    nil: quote::quote!(true),
+                    cons: quote::quote! {
+                        let #value = #head;
+                        #other <= &#value && #tail #after(#(#args,)* &#value)
+                    },
+                },
+            )
+            .with_input(syn::parse_quote!(#other: &#output))
+            .with_output(syn::parse_quote!(-> bool))
+            .with_where_predicates(partial_ord()),
+        );
+    }
+
+    if is_ordering(output) {
+        let cmp = ctx.ident("cmp_");
+        companions.push(ctx.companion(
+            "cmp_",
+            CompanionBody::Recursive {
+                nil: quote::quote!(core::cmp::Ordering::Equal),
+                cons: quote::quote! {
+                    match #head {
+                        core::cmp::Ordering::Equal => #tail #cmp(#(#args),*),
+                        #other => #other,
+                    }
+                },
+            },
+        ));
+    }
+    companions
+}
+
+/// `all_`, `any_`, `count_`, `none_`, `exactly_one_` and `mask_` for `bool` outputs.
+pub fn boolean(ctx: &CompanionContext) -> Vec<Companion> {
+    let CompanionContext { head, tail, this, args, .. } = ctx;
+    let (all, any) = (ctx.ident("all_"), ctx.ident("any_"));
+    let (count, mask) = (ctx.ident("count_"), ctx.ident("__mask_"));
+    let name = ctx.ident("mask_").to_string();
+    let (value, other) = (crate::hygiene::value(), crate::hygiene::other());
+    vec![
+        ctx.companion(
+            "all_",
+            CompanionBody::Recursive {
+                nil: quote::quote!(true),
+                cons: quote::quote!(#head && #tail #all(#(#args),*)),
+            },
+        ),
+        ctx.companion(
+            "any_",
+            CompanionBody::Recursive {
+                nil: quote::quote!(false),
+                cons: quote::quote!(#head || #tail #any(#(#args),*)),
+            },
+        ),
+        ctx.companion(
+            "count_",
+            CompanionBody::Recursive {
+                nil: quote::quote!(0),
+                cons: quote::quote!(#head as usize + #tail #count(#(#args),*)),
+            },
+        )
+        .with_output(syn::parse_quote!(-> usize)),
+        ctx.companion("none_", CompanionBody::Provided(quote::quote!(!#this #any(#(#args),*))))
+            .with_output(syn::parse_quote!(-> bool)),
+        ctx.companion(
+            "exactly_one_",
+            CompanionBody::Provided(quote::quote!(#this #count(#(#args),*) == 1)),
+        )
+        .with_output(syn::parse_quote!(-> bool)),
+        // The length is checked once at compile time, so the levels of `__mask_` just shift.
+        ctx.companion(
+            "__mask_",
+            CompanionBody::Recursive {
+                nil: quote::quote!(0),
+                cons: quote::quote! {
+                    let #value = #head as u64;
+                    let #other = #tail #mask(#(#args),*);
+                    #value | #other << 1
+                },
+            },
+        )
+        .with_output(syn::parse_quote!(-> u64)),
+        ctx.companion(
+            "mask_",
+            CompanionBody::Provided(quote::quote! {
+                const { assert!(Self::LEN <= 64, concat!("`", #name, "` only fits lists of up to 64 elements.")) };
+                #this #mask(#(#args),*)
+            }),
+        )
+        .with_output(syn::parse_quote!(-> u64)),
+    ]
+}
+
+/// `fold_` and `scan_`, threading an accumulator through the outputs with a closure.
+///
+/// Only generated for outputs that are the same for every element.
+pub fn fold(ctx: &CompanionContext) -> Vec<Companion> {
+    let CompanionContext { output, head, tail, args, generic_args, .. } = ctx;
+    if ctx.output_mentions_self || matches!(ctx.sig.output, syn::ReturnType::Default) {
+        return vec![];
+    }
+    let (fold, scan) = (ctx.ident("fold_"), ctx.ident("scan_"));
+    let (acc, acc_ty, f, f_ty) = (
+        crate::hygiene::acc(),
+        crate::hygiene::acc_ty(),
+        crate::hygiene::fold(),
+        crate::hygiene::fold_ty(),
+    );
+    let params: [syn::GenericParam; 2] = [
+        syn::parse_quote!(#acc_ty),
+        syn::parse_quote!(#f_ty: FnMut(#acc_ty, #output) -> #acc_ty),
+    ];
+    let inputs: [syn::FnArg; 2] = [syn::parse_quote!(#acc: #acc_ty), syn::parse_quote!(#f: #f_ty)];
+
+    let scan_output = ctx.assoc_type("scan_", &syn::parse_quote!(#acc_ty), &[syn::parse_quote!(#acc_ty)]);
+    let scan_output_ident = &scan_output.ident;
+
+    vec![
+        ctx.companion(
+            "fold_",
+            CompanionBody::Recursive {
+                nil: quote::quote!(#acc),
+                cons: quote::quote! {
+                    let mut #f = #f;
+                    let #acc = #f(#acc, #head);
+                    #tail #fold(#(#args,)* #acc, #f)
+                },
+            },
+        )
+        .with_generic_params(params.clone())
+        .with_input(inputs[0].clone())
+        .with_input(inputs[1].clone())
+        .with_output(syn::parse_quote!(-> #acc_ty)),
+        ctx.companion(
+            "scan_",
+            CompanionBody::Recursive {
+                nil: quote::quote!(hlist2::Nil),
+                cons: quote::quote! {
+                    let mut #f = #f;
+                    let #acc = #f(#acc, #head);
+                    hlist2::Cons(Clone::clone(&#acc), #tail #scan(#(#args,)* #acc, #f))
+                },
+            },
+        )
+        .with_generic_params(params)
+        .with_input(inputs[0].clone())
+        .with_input(inputs[1].clone())
+        .with_output(syn::parse_quote!(-> Self::#scan_output_ident<#(#generic_args,)* #acc_ty>))
+        .with_where_predicates([syn::parse_quote!(#acc_ty: Clone)])
+        .with_assoc_type(scan_output),
+    ]
+}
+
+fn is_primitive(ty: &syn::Type, names: &[&str]) -> bool {
+    matches!(ty, syn::Type::Path(ty) if ty.qself.is_none() && names.iter().any(|name| ty.path.is_ident(name)))
+}
+
+fn is_ordering(ty: &syn::Type) -> bool {
+    matches!(
+        ty,
+        syn::Type::Path(ty) if ty.qself.is_none()
+            && ty.path.segments.last().is_some_and(|last| last.ident == "Ordering" && last.arguments.is_none())
+    )
+}
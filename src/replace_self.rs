@@ -0,0 +1,31 @@
+pub fn replace_self(tokens: proc_macro2::TokenStream, with: &syn::Ident) -> proc_macro2::TokenStream {
+    tokens
+        .into_iter()
+        .map(|tree| match tree {
+            proc_macro2::TokenTree::Ident(ident) if ident == "Self" => {
+                proc_macro2::TokenTree::Ident(syn::Ident::new(&with.to_string(), ident.span()))
+            }
+            proc_macro2::TokenTree::Group(group) => {
+                let mut replaced = proc_macro2::Group::new(
+                    group.delimiter(),
+                    replace_self(group.stream(), with),
+                );
+                replaced.set_span(group.span());
+                proc_macro2::TokenTree::Group(replaced)
+            }
+            other => other,
+        })
+        .collect()
+}
+
+pub fn mentions_self(tokens: proc_macro2::TokenStream) -> bool {
+    mentions_ident(tokens, "Self")
+}
+
+pub fn mentions_ident(tokens: proc_macro2::TokenStream, name: &str) -> bool {
+    tokens.into_iter().any(|tree| match tree {
+        proc_macro2::TokenTree::Ident(ident) => ident == name,
+        proc_macro2::TokenTree::Group(group) => mentions_ident(group.stream(), name),
+        _ => false,
+    })
+}
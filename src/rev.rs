@@ -0,0 +1,58 @@
+//! Companions evaluating the elements from the last to the first.
+
+use crate::companion::{Companion, CompanionBody, CompanionContext};
+
+/// `rev_`, and `rev_all_` and `rev_any_` for `bool` outputs, when requested with `#[hlist(rev)]`.
+///
+/// The tail is called before the head, but outputs stay in list order.
+/// `hlist_fn_sig` is the signature of the elementwise method, whose output `rev_` shares.
+pub fn rev(
+    ctx: &CompanionContext,
+    hlist_fn_sig: &syn::Signature,
+    chain: bool,
+    returns_bool: bool,
+    requested: impl Fn(&str) -> bool,
+) -> Vec<Companion> {
+    let CompanionContext { head_last, tail, args_cloned, .. } = ctx;
+    if !["rev", "rev_all", "rev_any"].into_iter().any(requested) {
+        return vec![];
+    }
+    let (rev, rev_all, rev_any) = (ctx.ident("rev_"), ctx.ident("rev_all_"), ctx.ident("rev_any_"));
+    let other = crate::hygiene::other();
+    let body = if chain {
+        CompanionBody::Recursive {
+            nil: quote::quote!(self),
+            cons: quote::quote! {
+                #tail #rev(#(#args_cloned),*);
+                #head_last;
+                self
+            },
+        }
+    } else {
+        CompanionBody::Recursive {
+            nil: quote::quote!(hlist2::Nil),
+            cons: quote::quote! {
+                let #other = #tail #rev(#(#args_cloned),*);
+                hlist2::Cons(#head_last, #other)
+            },
+        }
+    };
+    let mut companions = vec![ctx.companion("rev_", body).with_output(hlist_fn_sig.output.clone())];
+    if returns_bool {
+        companions.push(ctx.companion(
+            "rev_all_",
+            CompanionBody::Recursive {
+                nil: quote::quote!(true),
+                cons: quote::quote!(#tail #rev_all(#(#args_cloned),*) && #head_last),
+            },
+        ));
+        companions.push(ctx.companion(
+            "rev_any_",
+            CompanionBody::Recursive {
+                nil: quote::quote!(false),
+                cons: quote::quote!(#tail #rev_any(#(#args_cloned),*) || #head_last),
+            },
+        ));
+    }
+    companions
+}
@@ -0,0 +1,64 @@
+//! Companions looking for the first element with a matching output.
+
+use crate::companion::{Companion, CompanionBody, CompanionContext};
+
+/// `position_` and `rposition_` for `bool` outputs, `find_map_` for `Option` outputs.
+///
+/// Elements after the match are not evaluated.
+pub fn search(ctx: &CompanionContext, returns_bool: bool) -> Vec<Companion> {
+    let CompanionContext { output, head, head_last, tail, args, args_cloned, .. } = ctx;
+    let (position, rposition, find_map) = (ctx.ident("position_"), ctx.ident("rposition_"), ctx.ident("find_map_"));
+    let (index, value) = (crate::hygiene::index(), crate::hygiene::value());
+    let mut companions = vec![];
+
+    if returns_bool {
+        companions.push(
+            ctx.companion(
+                "position_",
+                CompanionBody::Recursive {
+                    nil: quote::quote!(core::option::Option::None),
+                    cons: quote::quote! {
+                        if #head {
+                            core::option::Option::Some(0)
+                        } else {
+                            core::option::Option::map(#tail #position(#(#args),*), |#index| #index + 1)
+                        }
+                    },
+                },
+            )
+            .with_output(syn::parse_quote!(-> core::option::Option<usize>)),
+        );
+        // Searching from the back, so the tail is called before the head.
+        companions.push(
+            ctx.companion(
+                "rposition_",
+                CompanionBody::Recursive {
+                    nil: quote::quote!(core::option::Option::None),
+                    cons: quote::quote! {
+                        match #tail #rposition(#(#args_cloned),*) {
+                            core::option::Option::Some(#index) => core::option::Option::Some(#index + 1),
+                            core::option::Option::None => #head_last.then_some(0),
+                        }
+                    },
+                },
+            )
+            .with_output(syn::parse_quote!(-> core::option::Option<usize>)),
+        );
+    }
+
+    if crate::fallible::generic_args_of::<1>(output, "Option").is_some() && !ctx.output_mentions_self {
+        companions.push(ctx.companion(
+            "find_map_",
+            CompanionBody::Recursive {
+                nil: quote::quote!(core::option::Option::None),
+                cons: quote::quote! {
+                    match #head {
+                        core::option::Option::Some(#value) => core::option::Option::Some(#value),
+                        core::option::Option::None => #tail #find_map(#(#args),*),
+                    }
+                },
+            },
+        ));
+    }
+    companions
+}
@@ -0,0 +1,128 @@
+/// Hidden trait with the listed signature, implemented for every implementor of the base trait
+/// by calling the base trait method. Generated code calls the method through it, so that
+/// a mismatch between the listed signature and the base trait method is reported once,
+/// at the listed method, naming the base trait method.
+///
+/// Both methods are coerced to fn pointers and compared with a local trait marked with
+/// `#[diagnostic::on_unimplemented]`, implemented only for identical types. Signatures mentioning
+/// `impl Trait` can't be compared this way, and neither can `async`, `unsafe` or `extern` methods,
+/// so these call the base trait method directly.
+pub fn call_trait(
+    trait_generic_params: &[syn::GenericParam],
+    trait_where_clause: &Option<syn::WhereClause>,
+    base_trait: &syn::Path,
+    method: &crate::TraitHListMethod,
+) -> proc_macro2::TokenStream {
+    let crate::TraitHListMethod {
+        call_trait,
+        signature_trait,
+        item_sig,
+        item_fn_ident,
+        ..
+    } = method;
+
+    let trait_generic_params: Vec<_> = trait_generic_params
+        .iter()
+        .cloned()
+        .map(|mut param| {
+            match &mut param {
+                syn::GenericParam::Type(param) => {
+                    param.eq_token = None;
+                    param.default = None;
+                }
+                syn::GenericParam::Const(param) => {
+                    param.eq_token = None;
+                    param.default = None;
+                }
+                syn::GenericParam::Lifetime(_) => {}
+            }
+            param
+        })
+        .collect();
+    let trait_generic_args: Vec<_> = trait_generic_params
+        .iter()
+        .cloned()
+        .map(crate::generic_param_to_arg::generic_param_to_arg)
+        .collect();
+
+    let turbofish_args: Vec<_> = item_sig
+        .generics
+        .params
+        .iter()
+        .filter(|param| !matches!(param, syn::GenericParam::Lifetime(_)))
+        .cloned()
+        .map(crate::generic_param_to_arg::generic_param_to_arg)
+        .collect();
+    let turbofish = (!turbofish_args.is_empty()).then(|| quote::quote!(::<#(#turbofish_args),*>));
+    let base_fn = quote::quote!(<Self as #base_trait>::#item_fn_ident #turbofish);
+
+    let args: Vec<_> = item_sig
+        .inputs
+        .iter()
+        .map(|fn_arg| match fn_arg {
+            syn::FnArg::Receiver(receiver) => quote::quote_spanned!(receiver.self_token.span=> self),
+            syn::FnArg::Typed(syn::PatType { pat, .. }) => quote::quote!(#pat),
+        })
+        .collect();
+    let head_ty = crate::hygiene::head_ty();
+
+    let span = item_fn_ident.span();
+    let is_opaque = crate::replace_self::mentions_ident(quote::ToTokens::to_token_stream(item_sig), "impl");
+    if is_opaque || item_sig.asyncness.is_some() || item_sig.unsafety.is_some() || item_sig.abi.is_some() {
+        let body = quote::quote_spanned!(span=> #base_fn(#(#args),*));
+        return quote::quote! {
+            trait #call_trait<#(#trait_generic_params),*>: #base_trait + Sized #trait_where_clause {
+                #item_sig {
+                    #body
+                }
+            }
+
+            impl<#(#trait_generic_params,)* #head_ty: #base_trait> #call_trait<#(#trait_generic_args),*> for #head_ty
+                #trait_where_clause
+            {
+            }
+        };
+    }
+
+    let base_name = base_trait
+        .segments
+        .last()
+        .map(|segment| segment.ident.to_string())
+        .unwrap_or_default();
+    let message = format!("the signature of `{item_fn_ident}` does not match `{base_name}::{item_fn_ident}`");
+    let label = format!("listed as `{{Self}}`, but `{base_name}::{item_fn_ident}` is `{{T}}`");
+    let placeholders = args.iter().map(|_| quote::quote!(_));
+    let fn_ptr = quote::quote!(fn(#(#placeholders),*) -> _);
+    let check = quote::quote_spanned!(span=> #signature_trait::check);
+    let (listed, base, signatures) = (crate::hygiene::listed(), crate::hygiene::base(), crate::hygiene::signatures());
+    let mut signatures_at_method = signatures.clone();
+    signatures_at_method.set_span(signatures.span().located_at(span));
+    quote::quote! {
+        #[diagnostic::on_unimplemented(message = #message, label = #label)]
+        trait #signature_trait<T>: Sized {
+            fn check(signatures: (Self, T)) -> Self;
+        }
+
+        impl<T> #signature_trait<T> for T {
+            fn check((_, #base): (T, T)) -> T {
+                #base
+            }
+        }
+
+        trait #call_trait<#(#trait_generic_params),*>: #base_trait + Sized #trait_where_clause {
+            #item_sig {
+                // Both signatures are known before the call, so a mismatch fails the bound
+                // instead of being inferred into a type error on one of the arguments.
+                let #listed: #fn_ptr = <Self as #call_trait<#(#trait_generic_args),*>>::#item_fn_ident #turbofish;
+                let #base: #fn_ptr = #base_fn;
+                let #signatures = (#listed, #base);
+                #check(#signatures_at_method)(#(#args),*)
+            }
+        }
+
+        impl<#(#trait_generic_params,)* #head_ty: #base_trait> #call_trait<#(#trait_generic_args),*> for #head_ty
+            #trait_where_clause
+        {
+        }
+    }
+}
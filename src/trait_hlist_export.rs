@@ -0,0 +1,72 @@
+pub struct TraitHListExport {
+    pub item: syn::ItemTrait,
+    pub macro_ident: syn::Ident,
+}
+
+impl TraitHListExport {
+    pub fn new(macro_ident: Option<syn::Ident>, item: syn::ItemTrait) -> Self {
+        let macro_ident = macro_ident
+            .unwrap_or_else(|| quote::format_ident!("__{}_sigs", to_snake_case(&item.ident.to_string())));
+        Self { item, macro_ident }
+    }
+
+    pub fn expand(&self) -> proc_macro2::TokenStream {
+        let Self { item, macro_ident } = self;
+
+        let ident = &item.ident;
+        let generics = &item.generics;
+        let where_clause = &item.generics.where_clause;
+        let methods = item.items.iter().filter_map(|item| match item {
+            syn::TraitItem::Fn(syn::TraitItemFn { sig, .. }) => Some(quote::quote! { #sig; }),
+            _ => None,
+        });
+
+        // Paths relative to the exporting crate have to be resolved through `$crate` downstream.
+        let signatures = crate_to_dollar_crate(quote::quote! {
+            trait #ident #generics #where_clause {
+                #(#methods)*
+            }
+        });
+
+        quote::quote! {
+            #item
+
+            #[doc(hidden)]
+            #[macro_export]
+            macro_rules! #macro_ident {
+                ($($callback:ident)::+ { $($input:tt)* }) => {
+                    $($callback)::+ ! { @signatures { #signatures } $($input)* }
+                };
+            }
+        }
+    }
+}
+
+fn to_snake_case(ident: &str) -> String {
+    let mut snake = String::new();
+    let mut previous_lowercase = false;
+    for c in ident.chars() {
+        if c.is_uppercase() && previous_lowercase {
+            snake.push('_');
+        }
+        previous_lowercase = c.is_lowercase() || c.is_ascii_digit();
+        snake.extend(c.to_lowercase());
+    }
+    snake
+}
+
+fn crate_to_dollar_crate(tokens: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    tokens
+        .into_iter()
+        .map(|tree| match tree {
+            proc_macro2::TokenTree::Ident(ident) if ident == "crate" => quote::quote!($#ident),
+            proc_macro2::TokenTree::Group(group) => {
+                let mut replaced =
+                    proc_macro2::Group::new(group.delimiter(), crate_to_dollar_crate(group.stream()));
+                replaced.set_span(group.span());
+                quote::quote!(#replaced)
+            }
+            other => quote::quote!(#other),
+        })
+        .collect()
+}
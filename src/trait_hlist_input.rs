@@ -1,14 +1,40 @@
 pub struct TraitHListInput {
     pub vis: Option<syn::Token![pub]>,
     pub hlist_trait: syn::Ident,
-    pub base_trait: syn::Ident,
+    pub base_trait: syn::Path,
     pub trait_generic_params: Vec<syn::GenericParam>,
     pub trait_where_clause: Option<syn::WhereClause>,
     pub methods: Vec<crate::TraitHListMethod>,
+    /// Macro exported by `#[trait_hlist_export]` together with the input to pass back to it.
+    pub signatures_macro: Option<(syn::Path, proc_macro2::TokenStream)>,
 }
 
 impl syn::parse::Parse for TraitHListInput {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        // Signatures exported by `#[trait_hlist_export]` are passed back as `@signatures { trait ... }`.
+        let exported_trait: Option<syn::ItemTrait> = if input.peek(syn::Token![@]) {
+            input.parse::<syn::Token![@]>()?;
+            let keyword: syn::Ident = input.parse()?;
+            if keyword != "signatures" {
+                return Err(syn::Error::new(keyword.span(), "Expected `@signatures`."));
+            }
+            let inner;
+            syn::braced!(inner in input);
+            Some(inner.parse()?)
+        } else {
+            None
+        };
+        let tokens: proc_macro2::TokenStream = input.fork().parse()?;
+
+        // `#[hlist(...)]` before the header sets defaults for every method.
+        let mut defaults = crate::options::HListOptions::default();
+        for attr in input.call(syn::Attribute::parse_outer)? {
+            match &attr.meta {
+                syn::Meta::List(list) if list.path.is_ident("hlist") => defaults.parse(list, true)?,
+                _ => return Err(syn::Error::new_spanned(attr, "Expected `#[hlist(...)]`.")),
+            }
+        }
+
         let vis = if input.peek(syn::Token![pub]) {
             Some(input.parse()?)
         } else {
@@ -17,45 +43,115 @@ impl syn::parse::Parse for TraitHListInput {
         let hlist_trait: syn::Ident = input.parse()?;
         input.parse::<syn::Token![for]>()?;
         input.parse::<syn::Token![trait]>()?;
-        let base_trait: syn::Ident = input.parse()?;
+        let mut base_trait = syn::Path::parse_mod_style(input)?;
 
-        let trait_generic_params = if input.peek(syn::Token![<]) {
+        let mut trait_generic_params = if input.peek(syn::Token![<]) {
             let bracketed: crate::AngleBracketedGenericParams = input.parse()?;
             bracketed.params.into_iter().collect()
         } else {
             vec![]
         };
 
-        let trait_where_clause: Option<syn::WhereClause> = if input.peek(syn::Token![where]) {
+        let mut trait_where_clause: Option<syn::WhereClause> = if input.peek(syn::Token![where]) {
             Some(input.parse()?)
         } else {
             None
         };
 
-        let inner;
-        syn::braced!(inner in input);
+        if input.peek(syn::Token![use]) {
+            input.parse::<syn::Token![use]>()?;
+            let signatures_macro = syn::Path::parse_mod_style(input)?;
+            let _: proc_macro2::TokenStream = input.parse()?;
+            return Ok(Self {
+                hlist_trait,
+                base_trait,
+                vis,
+                methods: vec![],
+                trait_generic_params,
+                trait_where_clause,
+                signatures_macro: Some((signatures_macro, without_use_clause(tokens))),
+            });
+        }
+
+        // Signatures of well-known traits are only used when the body refers to them,
+        // so that a fully written out body keeps working for any trait with the same name.
+        let mut known_trait = exported_trait
+            .map(|item| crate::builtin_traits::KnownTrait::new(base_trait.clone(), item));
+
         let mut methods = Vec::new();
-        while !inner.is_empty() {
-            match inner.parse()? {
-                syn::TraitItem::Fn(trait_item_fn) => {
-                    methods.push(crate::TraitHListMethod::new(trait_item_fn));
-                }
-                syn::TraitItem::Const(_trait_item_const) => {
-                    panic!("Const items in traits are not supported.")
-                }
-                syn::TraitItem::Type(_trait_item_type) => {
-                    panic!("Type items in traits are not supported.")
-                }
-                syn::TraitItem::Macro(_trait_item_macro) => {
-                    panic!("Macro items in traits are not supported.")
+        if input.peek(syn::Token![;]) {
+            input.parse::<syn::Token![;]>()?;
+            let known_trait = resolve_known_trait(
+                &mut known_trait,
+                &mut base_trait,
+                &mut trait_generic_params,
+                &mut trait_where_clause,
+            )?;
+            for method in &known_trait.methods {
+                methods.push(method.clone());
+            }
+        } else {
+            let inner;
+            syn::braced!(inner in input);
+            while !inner.is_empty() {
+                let attrs = inner.call(syn::Attribute::parse_outer)?;
+
+                // Shorthand `fn name;` refers to a known signature of the base trait.
+                if inner.peek(syn::Token![fn]) && inner.peek2(syn::Ident) && inner.peek3(syn::Token![;]) {
+                    inner.parse::<syn::Token![fn]>()?;
+                    let ident: syn::Ident = inner.parse()?;
+                    inner.parse::<syn::Token![;]>()?;
+                    let known_trait = resolve_known_trait(
+                        &mut known_trait,
+                        &mut base_trait,
+                        &mut trait_generic_params,
+                        &mut trait_where_clause,
+                    )?;
+                    let Some(mut method) = known_trait.method(&ident) else {
+                        return Err(syn::Error::new(
+                            ident.span(),
+                            format!("No known signature for method `{ident}`, write out its full signature."),
+                        ));
+                    };
+                    method.attrs = attrs;
+                    methods.push(method);
+                    continue;
                 }
-                syn::TraitItem::Verbatim(_token_stream) => {
-                    panic!("Extra tokens in traits are not supported.")
+
+                match inner.parse()? {
+                    syn::TraitItem::Fn(mut trait_item_fn) => {
+                        trait_item_fn.attrs.splice(0..0, attrs);
+                        methods.push(trait_item_fn);
+                    }
+                    syn::TraitItem::Const(_trait_item_const) => {
+                        panic!("Const items in traits are not supported.")
+                    }
+                    syn::TraitItem::Type(_trait_item_type) => {
+                        panic!("Type items in traits are not supported.")
+                    }
+                    syn::TraitItem::Macro(_trait_item_macro) => {
+                        panic!("Macro items in traits are not supported.")
+                    }
+                    syn::TraitItem::Verbatim(_token_stream) => {
+                        panic!("Extra tokens in traits are not supported.")
+                    }
+                    _ => panic!("Unsupported item in trait."),
                 }
-                _ => panic!("Unsupported item in trait."),
             }
         }
 
+        // Methods are created once the base trait is final, since they call into it.
+        let trait_generic_args: Vec<_> = trait_generic_params
+            .clone().into_iter()
+            .map(crate::generic_param_to_arg::generic_param_to_arg)
+            .collect();
+        let base_trait_with_args: syn::Path = syn::parse_quote!(#base_trait<#(#trait_generic_args),*>);
+        let methods = methods
+            .into_iter()
+            .map(|method| crate::TraitHListMethod::new(method, &hlist_trait, &base_trait_with_args, &defaults))
+            .collect::<syn::Result<Vec<_>>>()?;
+        check_name_collisions(&methods)?;
+
         Ok(Self {
             hlist_trait,
             base_trait,
@@ -63,10 +159,87 @@ impl syn::parse::Parse for TraitHListInput {
             methods,
             trait_generic_params,
             trait_where_clause,
+            signatures_macro: None,
         })
     }
 }
 
+/// Reports every generated method or associated type whose name is already taken
+/// by something generated for an earlier method.
+fn check_name_collisions(methods: &[crate::TraitHListMethod]) -> syn::Result<()> {
+    let mut taken = std::collections::HashMap::new();
+    let mut errors: Option<syn::Error> = None;
+    for method in methods {
+        for (ident, description) in method.generated_idents() {
+            if let Some((other_method, other_description)) = taken.get(ident) {
+                let error = syn::Error::new(
+                    method.hlist_fn_ident.span(),
+                    format!(
+                        "The {description} `{ident}` generated for `{}` collides with the {other_description} generated for `{other_method}`.",
+                        method.hlist_fn_ident,
+                    ),
+                );
+                match &mut errors {
+                    Some(errors) => errors.combine(error),
+                    None => errors = Some(error),
+                }
+            } else {
+                taken.insert(ident.clone(), (&method.hlist_fn_ident, description));
+            }
+        }
+    }
+    errors.map_or(Ok(()), Err)
+}
+
+/// Removes `use path::to::macro` from the macro input, leaving everything else as is.
+fn without_use_clause(tokens: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    let mut tokens = tokens.into_iter().peekable();
+    let mut result = proc_macro2::TokenStream::new();
+    while let Some(token) = tokens.next() {
+        match &token {
+            proc_macro2::TokenTree::Ident(ident) if ident == "use" => {
+                while tokens
+                    .next_if(|token| match token {
+                        proc_macro2::TokenTree::Ident(_) => true,
+                        proc_macro2::TokenTree::Punct(punct) => punct.as_char() == ':',
+                        _ => false,
+                    })
+                    .is_some()
+                {}
+            }
+            _ => result.extend([token]),
+        }
+    }
+    result
+}
+
+/// Looks up known signatures of the base trait once, and switches the header over to them.
+///
+/// The base trait path is replaced by the canonical one, and trait generics are taken
+/// from the known trait unless they were given explicitly.
+fn resolve_known_trait<'a>(
+    known_trait: &'a mut Option<crate::builtin_traits::KnownTrait>,
+    base_trait: &mut syn::Path,
+    trait_generic_params: &mut Vec<syn::GenericParam>,
+    trait_where_clause: &mut Option<syn::WhereClause>,
+) -> syn::Result<&'a crate::builtin_traits::KnownTrait> {
+    if known_trait.is_none() {
+        let Some(found) = crate::builtin_traits::builtin_trait(base_trait) else {
+            return Err(syn::Error::new_spanned(
+                &*base_trait,
+                "No known signatures for this trait, list its methods in a `{ ... }` block.",
+            ));
+        };
+        *base_trait = found.path.clone();
+        if trait_generic_params.is_empty() && trait_where_clause.is_none() {
+            *trait_generic_params = found.generic_params.clone();
+            *trait_where_clause = found.where_clause.clone();
+        }
+        *known_trait = Some(found);
+    }
+    Ok(known_trait.as_ref().unwrap())
+}
+
 impl TraitHListInput {
     pub fn expand(&self) -> proc_macro2::TokenStream {
         let Self {
@@ -76,39 +249,38 @@ impl TraitHListInput {
             methods,
             trait_generic_params,
             trait_where_clause,
+            signatures_macro,
         } = self;
 
+        if let Some((signatures_macro, tokens)) = signatures_macro {
+            return quote::quote! {
+                #signatures_macro! { hlist2_trait_macro::TraitHList { #tokens } }
+            };
+        }
+
         let method_defs = methods.iter().map(
-            |crate::TraitHListMethod {
+            |method @ crate::TraitHListMethod {
                  hlist_fn_sig,
                  hlist_output_ident,
                  item_generic_params,
                  item_where_clause,
+                 per_element,
                  ..
              }| {
-                quote::quote! { type #hlist_output_ident <#(#item_generic_params),*> #item_where_clause; #hlist_fn_sig; }
+                let hlist_fn = match per_element {
+                    Some(body) => quote::quote! { #hlist_fn_sig { #body } },
+                    None => quote::quote! { #hlist_fn_sig; },
+                };
+                if !method.has_output_type() {
+                    return hlist_fn;
+                }
+                quote::quote! { type #hlist_output_ident <#(#item_generic_params),*> #item_where_clause; #hlist_fn }
             },
         );
        
-        let at_index_method_defs = methods.iter().map(
-            |crate::TraitHListMethod {
-                 hlist_fn_sig_at_index,
-                 ..
-             }| { quote::quote! { #hlist_fn_sig_at_index; } },
-        );
-
-        let bool_method_defs = methods.iter().filter_map(
-            |crate::TraitHListMethod {
-                 hlist_fn_sig_all,
-                 hlist_fn_sig_any,
-                 ..
-             }| {
-                let (Some(hlist_fn_sig_all), Some(hlist_fn_sig_any)) = (hlist_fn_sig_all, hlist_fn_sig_any) else { return None; };
-                Some(quote::quote! {
-                    #hlist_fn_sig_all;
-                    #hlist_fn_sig_any;
-                })
-            },
+        let (head_ty, tail_ty) = (
+            crate::hygiene::head_ty(),
+            crate::hygiene::tail_ty(),
         );
 
         let allow_unused_variables: syn::Attribute = syn::parse_quote!(#[allow(unused_variables)]);
@@ -118,38 +290,29 @@ impl TraitHListInput {
                  hlist_output_ident,
                  item_generic_params,
                  item_where_clause,
+                 chain,
+                 returns_self,
+                 per_element,
                  ..
              }| {
+                let hlist_fn = match (per_element, chain) {
+                    // Provided by the trait.
+                    (Some(_), _) => quote::quote!(),
+                    (None, true) => quote::quote! { #hlist_fn_sig { self } },
+                    (None, false) => quote::quote! { #hlist_fn_sig { hlist2::Nil } },
+                };
+                if *chain || *returns_self {
+                    return hlist_fn;
+                }
                 quote::quote! {
                     type #hlist_output_ident <#(#item_generic_params),*> = hlist2::Nil #item_where_clause; 
-                    #hlist_fn_sig { hlist2::Nil }
+                    #hlist_fn
                 }
             },
         );
-        let nil_at_index_impls = methods.iter().map(
-            |crate::TraitHListMethod {
-                 hlist_fn_sig_at_index,
-                 ..
-             }| { quote::quote! { #hlist_fn_sig_at_index { panic!("Index out of bounds, expected {__hlist_index} more items in the list.") } } },
-        );
-        let nil_bool_impls = methods.iter().filter_map(
-            |crate::TraitHListMethod {
-                 hlist_fn_sig_all,
-                 hlist_fn_sig_any,
-                 ..
-             }| {
-                let (Some(hlist_fn_sig_all), Some(hlist_fn_sig_any)) = (hlist_fn_sig_all, hlist_fn_sig_any) else { return None; };
-                Some(quote::quote! {
-                    #hlist_fn_sig_all {true}
-                    #hlist_fn_sig_any {false}
-                })
-            },
-        );
-
         let cons_impls = methods.iter().map(
             |crate::TraitHListMethod {
                  hlist_fn_sig,
-                 item_fn_ident,
                  hlist_fn_ident,
                  item_output,
                  hlist_output_ident,
@@ -158,92 +321,116 @@ impl TraitHListInput {
                  item_generic_params,
                 item_generic_args,
                  item_where_clause,
+                 split_self,
+                 head_fn,
+                 head_self,
+                 tail,
+                 chain,
+                 returns_self,
+                 item_sig,
+                 per_element,
                  ..
              }| {
-                 quote::quote! {
+                let output_type = (!*chain && !*returns_self).then(|| quote::quote! {
                     type #hlist_output_ident <#(#item_generic_params),*> 
-                        = hlist2::Cons<#item_output, __HListTail::#hlist_output_ident<#(#item_generic_args),*>> #item_where_clause; 
+                        = hlist2::Cons<#item_output, #tail_ty::#hlist_output_ident<#(#item_generic_args),*>> #item_where_clause; 
+                });
+                if per_element.is_some() {
+                    return quote::quote! { #output_type };
+                }
+                if *chain {
+                    // Splitting a reborrow, so that `self` can be returned afterwards.
+                    let (head, tail) = (crate::hygiene::head(), crate::hygiene::tail());
+                    let mutability = item_sig.receiver().and_then(|receiver| receiver.mutability);
+                    return quote::quote! {
+                        #hlist_fn_sig {
+                            let hlist2::Cons(#head, #tail) = &#mutability *self;
+                            #head_fn(#head, #(#args_cloned),*);
+                            #tail.#hlist_fn_ident(#(#args),*);
+                            self
+                        }
+                    };
+                }
+                 quote::quote! {
+                    #output_type
                      #hlist_fn_sig {
-                         let hlist2::Cons(__hlist_head, __hlist_tail) = self;
-                         hlist2::Cons(__hlist_head.#item_fn_ident(#(#args_cloned),*), __hlist_tail.#hlist_fn_ident(#(#args),*))
+                         #split_self
+                         hlist2::Cons(#head_fn(#head_self #(#args_cloned),*), #tail #hlist_fn_ident(#(#args),*))
                      }
                  }
             },
         );
-        let cons_at_index_impls = methods.iter().map(
-            |crate::TraitHListMethod {
-                 hlist_fn_sig_at_index,
-                 item_fn_ident,
-                 hlist_fn_ident_at_index,
-                 args,
-                 ..
-             }| {
-                quote::quote! {
-                    #hlist_fn_sig_at_index {
-                        let hlist2::Cons(__hlist_head, __hlist_tail) = self;
-                        if __hlist_index == 0 {
-                            __hlist_head.#item_fn_ident(#(#args),*)
-                        } else {
-                            __hlist_tail.#hlist_fn_ident_at_index(#(#args,)* __hlist_index - 1)
-                        }
-                    }
-                }
-            },
-        );
-        let cons_bool_impls = methods.iter().filter_map(
-            |crate::TraitHListMethod {
-                 hlist_fn_sig_all,
-                 hlist_fn_sig_any,
-                 hlist_fn_ident_all,
-                 hlist_fn_ident_any,
-                 args,
-                 args_cloned,
-                 item_fn_ident,
-                 ..
-             }| {
-                let (Some(hlist_fn_sig_all), Some(hlist_fn_sig_any)) = (hlist_fn_sig_all, hlist_fn_sig_any) else { return None; };
-                Some(quote::quote! {
-                    #hlist_fn_sig_all {
-                        let hlist2::Cons(__hlist_head, __hlist_tail) = self;
-                        __hlist_head.#item_fn_ident(#(#args_cloned),*) && __hlist_tail.#hlist_fn_ident_all(#(#args),*)
-                    }
-                    #hlist_fn_sig_any {
-                        let hlist2::Cons(__hlist_head, __hlist_tail) = self;
-                        __hlist_head.#item_fn_ident(#(#args_cloned),*) || __hlist_tail.#hlist_fn_ident_any(#(#args),*)
-                    }
-                })
-            },
-        );
+        let companion_defs = methods
+            .iter()
+            .flat_map(|method| method.companions.iter().map(crate::companion::Companion::trait_item));
+        let nil_companion_impls = methods
+            .iter()
+            .flat_map(|method| method.companions.iter().filter_map(crate::companion::Companion::nil_item));
+        let cons_companion_impls = methods.iter().flat_map(|method| {
+            method
+                .companions
+                .iter()
+                .filter_map(|companion| companion.cons_item(&method.split_self))
+        });
 
         let trait_generic_args: Vec<_> = trait_generic_params
             .clone().into_iter()
             .map(crate::generic_param_to_arg::generic_param_to_arg)
             .collect();
 
+        let base_trait_with_args: syn::Path = syn::parse_quote!(#base_trait<#(#trait_generic_args),*>);
+        let call_traits = methods.iter().map(|method| {
+            crate::signature_check::call_trait(trait_generic_params, trait_where_clause, &base_trait_with_args, method)
+        });
+
+        let (at_traits, at_impls): (Vec<_>, Vec<_>) = methods
+            .iter()
+            .filter_map(|method| {
+                crate::indexing::at_trait(vis, trait_generic_params, trait_where_clause, &base_trait_with_args, method)
+            })
+            .unzip();
+
+        // The call traits are implemented for every implementor of the base trait, so they are kept
+        // out of scope of the calling code, together with the impls calling through them.
         quote::quote! {
+            #(#at_traits)*
+
+            // Names lifted from signatures exported in the same crate carry the spans of the exported
+            // trait, which would report every companion left uncalled as dead code.
+            #[allow(dead_code)]
             #vis trait #hlist_trait<#(#trait_generic_params),*> #trait_where_clause {
+                /// Number of elements in the list.
+                const LEN: usize;
+
                 #(#method_defs)*
-                #(#at_index_method_defs)*
-                #(#bool_method_defs)*
+                #(#companion_defs)*
             }
 
+            const _: () = {
+            #(#call_traits)*
+
             #allow_unused_variables
             impl<#(#trait_generic_params),*>
                 #hlist_trait<#(#trait_generic_args),*> for hlist2::Nil #trait_where_clause {
+                const LEN: usize = 0;
+
                 #(#nil_impls)*
-                #(#nil_at_index_impls)*
-                #(#nil_bool_impls)*
+                #(#nil_companion_impls)*
             }
             //
             impl<
                 #(#trait_generic_params,)*
-                __HListHead: #base_trait <#(#trait_generic_args),*>,
-                __HListTail: #hlist_trait<#(#trait_generic_args),*>
-            > #hlist_trait<#(#trait_generic_args),*> for hlist2::Cons<__HListHead, __HListTail> #trait_where_clause {
+                #head_ty: #base_trait <#(#trait_generic_args),*>,
+                #tail_ty: #hlist_trait<#(#trait_generic_args),*>
+            > #hlist_trait<#(#trait_generic_args),*> for hlist2::Cons<#head_ty, #tail_ty> #trait_where_clause {
+                const LEN: usize = 1 + <#tail_ty as #hlist_trait<#(#trait_generic_args),*>>::LEN;
+
                 #(#cons_impls)*
-                #(#cons_at_index_impls)*
-                #(#cons_bool_impls)*
+                #(#cons_companion_impls)*
             }
+
+            #(#at_impls)*
+            };
         }
     }
 }
@@ -2,23 +2,42 @@
 pub struct TraitHListMethod {
     pub item_fn_ident: syn::Ident,
     pub hlist_fn_ident: syn::Ident,
-    pub hlist_fn_ident_at_index: syn::Ident,
-    pub hlist_fn_ident_all: Option<syn::Ident>,
-    pub hlist_fn_ident_any: Option<syn::Ident>,
 
     pub hlist_fn_sig: syn::Signature,
-    pub hlist_fn_sig_at_index: syn::Signature,
-    pub hlist_fn_sig_all: Option<syn::Signature>,
-    pub hlist_fn_sig_any: Option<syn::Signature>,
+    pub companions: Vec<crate::companion::Companion>,
+    /// Set by `#[hlist(enumerate)]` or `#[hlist(zip)]` on an argument: the body of the elementwise
+    /// method, which is provided on top of the hidden `__per_element_` companion.
+    pub per_element: Option<proc_macro2::TokenStream>,
 
+    pub item_sig: syn::Signature,
     pub item_output: syn::Type,
     pub item_generic_params: Vec<syn::GenericParam>,
     pub item_generic_args: Vec<syn::GenericArgument>,
     pub item_where_clause: Option<syn::WhereClause>,
     pub hlist_output_ident: syn::Ident,
+    /// Hidden trait dispatching `_at::<I>` to the element at a type-level index.
+    pub at_trait: syn::Ident,
+    /// Hidden trait with the listed signature, through which the base trait method is called.
+    pub call_trait: syn::Ident,
+    /// Local trait comparing the listed signature with the base trait method, named after the method.
+    pub signature_trait: syn::Ident,
+    /// Set by `#[hlist(chain)]`: the method returns the list itself instead of an hlist of `()`.
+    pub chain: bool,
+    /// Methods returning exactly `Self` return the list type itself, like builders.
+    pub returns_self: bool,
 
     pub args: Vec<proc_macro2::TokenStream>,
     pub args_cloned: Vec<proc_macro2::TokenStream>,
+
+    /// Destructuring of `self` into `__hlist_head` and `__hlist_tail`, empty for associated functions.
+    pub split_self: proc_macro2::TokenStream,
+    /// The base trait method called on the head, through the listed signature:
+    /// `<__HListHead as __XHListMethodCall<..>>::method`.
+    pub head_fn: proc_macro2::TokenStream,
+    /// Receiver argument for `head_fn`: `__hlist_head,`, empty for associated functions.
+    pub head_self: proc_macro2::TokenStream,
+    /// Prefix for calling the method on the tail: `__hlist_tail.` or `__HListTail::`.
+    pub tail: proc_macro2::TokenStream,
 }
 
 impl TraitHListMethod {
@@ -29,16 +48,28 @@ impl TraitHListMethod {
             default,
             semi_token: _,
         }: syn::TraitItemFn,
-    ) -> Self {
+        hlist_trait: &syn::Ident,
+        base_trait: &syn::Path,
+        defaults: &crate::options::HListOptions,
+    ) -> syn::Result<Self> {
         {
             assert!(
                 default.is_none(),
                 "Default implementation is not supported in methods."
             );
 
+            let mut sig = sig;
+            crate::elided_lifetimes::name_elided_output_lifetimes(&mut sig);
+
             let item_fn_ident = sig.ident.clone();
 
-            let mut hlist_fn_ident = item_fn_ident.clone();
+            let markers = crate::per_element::take_markers(&mut sig);
+            for (zipped, _) in markers.iter().filter(|(_, marker)| *marker == crate::per_element::Marker::Zip) {
+                crate::elided_lifetimes::name_elided_argument_lifetimes(&mut sig, zipped);
+            }
+
+            let mut hlist_fn_ident = None;
+            let mut options = crate::options::HListOptions::default();
 
             for attr in attrs {
                 match &attr {
@@ -57,23 +88,41 @@ impl TraitHListMethod {
                         ..
                     } if attrs2.is_empty() && key.is_ident("name") => {
                         if let Some(name) = value.get_ident() {
-                            hlist_fn_ident = name.clone()
+                            hlist_fn_ident = Some(name.clone())
                         } else {
                             panic!("Name must be a simple identifier without path.")
                         }
                     }
+                    syn::Attribute {
+                        meta: syn::Meta::List(list),
+                        ..
+                    } if list.path.is_ident("hlist") => {
+                        options.parse(list, false)?
+                    }
                     _ => panic!(
-                        "Unsupported method attribute or format. Try #[name = <other_method_name>] without quotes."
+                        "Unsupported method attribute or format. Try #[name = <other_method_name>] or #[hlist(<companion>, ...)]."
                     ),
                 }
             }
-            let hlist_fn_ident_at_index = quote::format_ident!("{}_at_index", hlist_fn_ident);
-
+            let options = options.with_defaults(defaults);
+            let hlist_fn_ident = hlist_fn_ident
+                .or_else(|| options.rename(&item_fn_ident))
+                .unwrap_or_else(|| item_fn_ident.clone());
+            let chain = options.chain;
             let item_output: syn::Type = match sig.output.clone() {
                 syn::ReturnType::Default => syn::parse_quote!(()),
                 syn::ReturnType::Type(_, ty) => *ty,
             };
 
+            // Outputs mentioning `Self` differ from element to element,
+            // so they cannot be returned by index.
+            let output_mentions_self = crate::replace_self::mentions_self(quote::ToTokens::to_token_stream(&item_output));
+            let item_output: syn::Type = syn::parse2(crate::replace_self::replace_self(
+                quote::ToTokens::to_token_stream(&item_output),
+                &crate::hygiene::head_ty(),
+            ))
+            .expect("Replacing `Self` keeps the output a valid type.");
+
             let hlist_output_ident =
                 quote::format_ident!("{}HListOutput", sig.ident.to_string().to_uppercase());
 
@@ -87,44 +136,64 @@ impl TraitHListMethod {
                 .map(crate::generic_param_to_arg::generic_param_to_arg)
                 .collect();
 
-            let mut hlist_fn_ident_all = None;
-            let mut hlist_fn_ident_any = None;
-            let mut hlist_fn_sig_all = None;
-            let mut hlist_fn_sig_any = None;
-
-            if let syn::Type::Path(ref ty) = item_output
-                && ty.qself.is_none()
-                && ty.path.is_ident("bool")
-            {
-                hlist_fn_ident_all = Some(quote::format_ident!("all_{}", hlist_fn_ident));
-                hlist_fn_ident_any = Some(quote::format_ident!("any_{}", hlist_fn_ident));
-                hlist_fn_sig_all = Some(syn::Signature {
-                    output: syn::parse_quote! {-> bool},
-                    ident: hlist_fn_ident_all.clone().unwrap(),
-                    ..sig.clone()
-                });
-                hlist_fn_sig_any = Some(syn::Signature {
-                    output: syn::parse_quote! {-> bool},
-                    ident: hlist_fn_ident_any.clone().unwrap(),
-                    ..sig.clone()
-                });
-            };
-
-            let hlist_fn_sig = syn::Signature {
+            let mut hlist_fn_sig = syn::Signature {
                 output: syn::parse_quote! { -> Self::#hlist_output_ident<#(#item_generic_args),*>},
                 ident: hlist_fn_ident.clone(),
                 ..sig.clone()
             };
+            let returns_self = matches!(&sig.output, syn::ReturnType::Type(_, ty) if matches!(&**ty, syn::Type::Path(ty) if ty.qself.is_none() && ty.path.is_ident("Self")));
+            if returns_self {
+                hlist_fn_sig.output = syn::parse_quote!(-> Self);
+            }
+            if chain {
+                assert!(
+                    matches!(&item_output, syn::Type::Tuple(tuple) if tuple.elems.is_empty()),
+                    "`#[hlist(chain)]` on `{item_fn_ident}` requires a method without output."
+                );
+                let receiver = sig
+                    .receiver()
+                    .unwrap_or_else(|| panic!("`#[hlist(chain)]` on `{item_fn_ident}` requires a `&self` or `&mut self` receiver."));
+                assert!(
+                    receiver.reference.is_some(),
+                    "`#[hlist(chain)]` on `{item_fn_ident}` can't return the list, since `self` consumes every element."
+                );
+                let ty = &receiver.ty;
+                hlist_fn_sig.output = syn::parse_quote!(-> #ty);
+            }
 
-            let hlist_fn_sig_at_index = syn::Signature {
-                ident: hlist_fn_ident_at_index.clone(),
-                inputs: {
-                    let mut inputs = sig.inputs.clone();
-                    inputs.push(syn::parse_quote!(__hlist_index: usize));
-                    inputs
-                },
-                ..sig.clone()
+            let (head, tail, head_ty, tail_ty) = (
+                crate::hygiene::head(),
+                crate::hygiene::tail(),
+                crate::hygiene::head_ty(),
+                crate::hygiene::tail_ty(),
+            );
+            let (split_self, head_self, tail) = if sig.receiver().is_some() {
+                (
+                    quote::quote!(let hlist2::Cons(#head, #tail) = self;),
+                    quote::quote!(#head,),
+                    quote::quote!(#tail.),
+                )
+            } else {
+                (
+                    quote::quote!(),
+                    quote::quote!(),
+                    quote::quote!(#tail_ty::),
+                )
             };
+            let camel_case: String = sig.ident.to_string().split('_').map(|word| {
+                let mut chars = word.chars();
+                chars.next().map(|first| first.to_uppercase().chain(chars).collect()).unwrap_or_default()
+            }).collect::<Vec<String>>().concat();
+            let (at_trait, call_trait) = (
+                quote::format_ident!("__{}{}At", hlist_trait, camel_case),
+                quote::format_ident!("__{}{}Call", hlist_trait, camel_case),
+            );
+            let signature_trait = crate::hygiene::signature_ty(&camel_case);
+            let trait_generic_args: Vec<syn::GenericArgument> = match &base_trait.segments.last().map(|segment| &segment.arguments) {
+                Some(syn::PathArguments::AngleBracketed(bracketed)) => bracketed.args.iter().cloned().collect(),
+                _ => vec![],
+            };
+            let head_fn = quote::quote!(<#head_ty as #call_trait<#(#trait_generic_args),*>>::#item_fn_ident);
 
             let mut args = vec![];
             let mut args_cloned = vec![];
@@ -147,24 +216,102 @@ impl TraitHListMethod {
                 }
             }
 
-            Self {
+            let ctx = crate::companion::CompanionContext {
+                sig: &sig,
+                name: &hlist_fn_ident,
+                output: &item_output,
+                output_mentions_self,
+                head: quote::quote!(#head_fn(#head_self #(#args_cloned),*)),
+                head_last: quote::quote!(#head_fn(#head_self #(#args),*)),
+                tail: &tail,
+                this: if sig.receiver().is_some() { quote::quote!(self.) } else { quote::quote!(Self::) },
+                args: &args,
+                args_cloned: &args_cloned,
+                generic_params: &item_generic_params,
+                generic_args: &item_generic_args,
+                where_clause: item_where_clause.as_ref(),
+                options: &options,
+            };
+            let requested = |name: &str| options.requested(name);
+            let returns_bool = matches!(&item_output, syn::Type::Path(ty) if ty.qself.is_none() && ty.path.is_ident("bool"));
+            let at_index = crate::indexing::at_index(&ctx, requested("get"));
+            let at_index_ident = at_index.iter().find(|companion| companion.key == "at_index").map(|at_index| &at_index.ident);
+            let (companions, per_element) = if !markers.is_empty() {
+                assert!(
+                    !options.requests_any(),
+                    "Companion methods are not generated for `{item_fn_ident}`, since it has `#[hlist(enumerate)]` or `#[hlist(zip)]` arguments."
+                );
+                let (per_element, body) =
+                    crate::per_element::per_element(&ctx, &mut hlist_fn_sig, chain, &markers, &head_fn, &head_self);
+                (vec![per_element], Some(body))
+            } else {
+                let companions = [
+                    Vec::from_iter(requested("at").then(|| crate::indexing::at(&ctx, &at_trait, &trait_generic_args))),
+                    at_index.clone(),
+                    Vec::from_iter(crate::indexing::at_indices(&ctx, at_index_ident)),
+                    if returns_bool { crate::reductions::boolean(&ctx) } else { vec![] },
+                    crate::rev::rev(&ctx, &hlist_fn_sig, chain, returns_bool, requested),
+                    crate::reductions::numeric(&ctx, requested),
+                    crate::reductions::ordering(&ctx, requested),
+                    crate::reductions::fold(&ctx),
+                    crate::collect::collect(&ctx, at_index_ident, requested("vec")),
+                    crate::join::join(&ctx, requested("join")),
+                    crate::unzip::unzip(&ctx),
+                    crate::flatten::flatten(&ctx, at_index_ident, requested),
+                    crate::search::search(&ctx, returns_bool),
+                    crate::fallible::fallible(&ctx, options.error_type.as_ref(), options.default_error_type.as_ref()),
+                ]
+                .concat();
+                (options.select(companions), None)
+            };
+
+            Ok(Self {
                 item_fn_ident,
                 hlist_fn_ident,
-                hlist_fn_ident_at_index,
-                hlist_fn_ident_all,
-                hlist_fn_ident_any,
                 hlist_fn_sig,
-                hlist_fn_sig_at_index,
-                hlist_fn_sig_all,
-                hlist_fn_sig_any,
+                companions,
+                per_element,
                 item_generic_params,
                 item_generic_args,
                 item_where_clause,
                 item_output,
                 hlist_output_ident,
+                at_trait,
+                call_trait,
+                signature_trait,
+                chain,
+                returns_self,
                 args,
                 args_cloned,
-            }
+                split_self,
+                head_fn,
+                head_self,
+                tail,
+                item_sig: sig,
+            })
         }
     }
+    /// Whether the elementwise method returns the `{METHOD}HListOutput` associated type.
+    pub fn has_output_type(&self) -> bool {
+        !self.chain && !self.returns_self
+    }
+
+    /// Names this method adds to the HList trait, each with a short description.
+    pub fn generated_idents(&self) -> Vec<(&syn::Ident, String)> {
+        [
+            (Some(&self.hlist_fn_ident), "method"),
+            (self.has_output_type().then_some(&self.hlist_output_ident), "output type"),
+        ]
+        .into_iter()
+        .filter_map(|(ident, description)| Some((ident?, description.to_string())))
+        .chain(self.companions.iter().flat_map(|companion| {
+            std::iter::once((&companion.ident, companion.description.clone())).chain(
+                companion
+                    .assoc_types
+                    .iter()
+                    .map(|assoc| (&assoc.ident, format!("output type of the {}", companion.description))),
+            )
+        }))
+        .collect()
+    }
 }
@@ -0,0 +1,49 @@
+//! Companion splitting tuple outputs into a tuple of lists.
+
+use crate::companion::{Companion, CompanionBody, CompanionContext};
+
+/// `unzip_` for outputs that are 2- or 3-tuples, with one output type per tuple position:
+/// `{METHOD}HListOutput0`, `{METHOD}HListOutput1`, ..
+pub fn unzip(ctx: &CompanionContext) -> Vec<Companion> {
+    let CompanionContext { output, head, tail, args, generic_args, .. } = ctx;
+    let syn::Type::Tuple(tuple) = output else { return vec![] };
+    if !(2..=3).contains(&tuple.elems.len()) {
+        return vec![];
+    }
+    let unzip = ctx.ident("unzip_");
+    let assoc_types: Vec<_> = tuple
+        .elems
+        .iter()
+        .enumerate()
+        .map(|(i, elem)| {
+            let ident = quote::format_ident!("{}HListOutput{}", ctx.sig.ident.to_string().to_uppercase(), i);
+            ctx.assoc_type_named(ident, elem, &[])
+        })
+        .collect();
+    let outputs = assoc_types.iter().map(|assoc| {
+        let ident = &assoc.ident;
+        quote::quote!(Self::#ident<#(#generic_args),*>)
+    });
+    let values: Vec<_> = (0..tuple.elems.len())
+        .map(|i| crate::hygiene::numbered(&crate::hygiene::value(), i))
+        .collect();
+    let others: Vec<_> = (0..tuple.elems.len())
+        .map(|i| crate::hygiene::numbered(&crate::hygiene::other(), i))
+        .collect();
+    let nils = values.iter().map(|_| quote::quote!(hlist2::Nil));
+
+    let companion = ctx
+        .companion(
+            "unzip_",
+            CompanionBody::Recursive {
+                nil: quote::quote!((#(#nils,)*)),
+                cons: quote::quote! {
+                    let (#(#values,)*) = #head;
+                    let (#(#others,)*) = #tail #unzip(#(#args),*);
+                    (#(hlist2::Cons(#values, #others),)*)
+                },
+            },
+        )
+        .with_output(syn::parse_quote!(-> (#(#outputs,)*)));
+    vec![assoc_types.into_iter().fold(companion, Companion::with_assoc_type)]
+}
@@ -0,0 +1,13 @@
+//! Trait exported with `#[trait_hlist_export]`, lifted by the integration tests of
+//! `hlist2_trait_macro` from another crate.
+
+pub struct Health(pub u32);
+
+#[hlist2_trait_macro::trait_hlist_export]
+pub trait Unit {
+    fn health(&self) -> crate::Health;
+    fn hit(&mut self, damage: u32) -> bool;
+    fn label(&self) -> &'static str {
+        "unit"
+    }
+}
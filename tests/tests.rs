@@ -72,6 +72,7 @@ fn generic_into_renamed() {
 }
 
 #[test]
+#[allow(clippy::into_iter_on_ref, clippy::multiple_bound_locations)]
 fn generic_trait_0() {
     trait MyTrait<const N: usize, T: Into<i64>> {
         fn a<U: Into<i64>>(&self, x: i64, y: U, z: T) -> bool;
@@ -234,23 +235,20 @@ fn push() {
 
 #[test]
 fn find_maximum() {
-    pub trait IndexMax<S> {
-        fn index_max(&self, index: &mut usize, max_index: &mut usize, max: &mut S);
+    pub trait Value {
+        fn value(&self) -> i32;
     }
 
-    impl IndexMax<i32> for i32 {
-        fn index_max(&self, index: &mut usize, max_index: &mut usize, max: &mut i32) {
-            if self > max {
-                *max = *self;
-                *max_index = *index;
-            }
-            *index += 1;
+    impl Value for i32 {
+        fn value(&self) -> i32 {
+            *self
         }
     }
 
     TraitHList! {
-        IndexMaxHList for trait IndexMax<S> {
-            fn index_max(&self, index: &mut usize, max_index: &mut usize, max: &mut S);
+        ValueHList for trait Value {
+            #[hlist(max, argmax)]
+            fn value(&self) -> i32;
         }
     }
 
@@ -271,12 +269,1649 @@ fn find_maximum() {
 
     let h = hlist![0,2,4,6,8,10,10,8,6,4,2,0];
 
-    let mut max_index = 0;
-    let mut max = -1;
-
-    h.index_max(&mut 0, &mut max_index, &mut max);
+    let max_index = h.argmax_value().unwrap();
     assert_eq!(max_index, 5);
-    assert_eq!(max, 10);
+    assert_eq!(h.max_value(), Some(10));
 
     assert_eq!(h.hlist_into_at_index(max_index), 10f64);
 }
+
+#[test]
+fn builtin_signatures() {
+    TraitHList! {
+        pub CloneHList for trait Clone {
+            #[name = clone_each]
+            fn clone;
+        }
+    }
+    TraitHList! { pub DefaultHList for trait Default; }
+    TraitHList! { pub ToStringHList for trait ToString; }
+    TraitHList! { pub IntoHList for trait Into; }
+    TraitHList! {
+        pub PartialEqHList for trait PartialEq {
+            #[name = eq_each]
+            fn eq;
+        }
+    }
+    TraitHList! { pub AsRefHList for trait std::convert::AsRef; }
+    TraitHList! { pub IteratorHList for trait Iterator; }
+    TraitHList! { pub DisplayHList for trait std::fmt::Display; }
+    TraitHList! { pub HashHList for trait Hash; }
+
+    struct Joined<L>(L);
+    impl<L: DisplayHList> std::fmt::Display for Joined<L> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            DisplayHList::fmt(&self.0, f);
+            Ok(())
+        }
+    }
+
+    let list = hlist![1u8, String::from("two"), 3.5f64];
+    assert_eq!(list.clone_each(), list);
+    assert_eq!(
+        <HList![u8, String, f64] as DefaultHList>::default(),
+        hlist![0u8, String::new(), 0f64]
+    );
+    assert_eq!(list.to_string(), hlist!["1".to_string(), "two".to_string(), "3.5".to_string()]);
+    assert_eq!(list.to_string_at_index(1), "two");
+
+    assert_eq!(Joined(hlist![1, "-", 2.5]).to_string(), "1-2.5");
+
+    let mut hasher = std::hash::DefaultHasher::new();
+    HashHList::hash(&hlist![1u8, "x"], &mut hasher);
+    let mut expected = std::hash::DefaultHasher::new();
+    std::hash::Hash::hash(&1u8, &mut expected);
+    std::hash::Hash::hash("x", &mut expected);
+    assert_eq!(std::hash::Hasher::finish(&hasher), std::hash::Hasher::finish(&expected));
+
+    let small = hlist![true, 2u8, 3u16];
+    assert_eq!(IntoHList::into(small), hlist![1u32, 2u32, 3u32]);
+
+    let numbers = hlist![1, 2, 3];
+    assert_eq!(numbers.eq_each(&2), hlist![false, true, false]);
+    assert!(numbers.any_eq_each(&3));
+    assert!(!numbers.all_eq_each(&3));
+
+    let strings = hlist![String::from("a"), "b", String::from("c")];
+    let refs: HList![&str, &str, &str] = AsRefHList::<str>::as_ref(&strings);
+    assert_eq!(refs, hlist!["a", "b", "c"]);
+
+    let mut iterators = hlist![0..2, "xy".chars(), vec![true].into_iter()];
+    assert_eq!(iterators.next(), hlist![Some(0), Some('x'), Some(true)]);
+    assert_eq!(iterators.next(), hlist![Some(1), Some('y'), None]);
+}
+
+#[hlist2_trait_macro::trait_hlist_export]
+pub trait Component {
+    fn update(&mut self, dt: f32) -> bool;
+    fn name(&self) -> &'static str {
+        "component"
+    }
+}
+
+#[test]
+fn exported_signatures() {
+    struct Timer(f32);
+    impl Component for Timer {
+        fn update(&mut self, dt: f32) -> bool {
+            self.0 -= dt;
+            self.0 > 0.0
+        }
+    }
+    struct Counter(u32);
+    impl Component for Counter {
+        fn update(&mut self, _dt: f32) -> bool {
+            self.0 += 1;
+            true
+        }
+        fn name(&self) -> &'static str {
+            "counter"
+        }
+    }
+
+    TraitHList! { pub ComponentHList for trait Component use __component_sigs; }
+    TraitHList! {
+        pub NamesHList for trait self::Component use __component_sigs {
+            #[name = names]
+            fn name;
+        }
+    }
+
+    let mut list = hlist![Timer(1.0), Counter(0)];
+    assert_eq!(list.update(0.5), hlist![true, true]);
+    assert!(!list.all_update(0.5));
+    assert!(list.any_update(0.5));
+    assert!(list.update_at_index(0.5, 1));
+    assert_eq!(list.name(), hlist!["component", "counter"]);
+    assert_eq!(list.name_at_index(0), "component");
+    assert_eq!(list.names(), hlist!["component", "counter"]);
+    assert_eq!(list.names_at_index(1), "counter");
+}
+
+#[test]
+fn signatures_exported_by_another_crate() {
+    use export_fixture::{Health, Unit};
+
+    struct Soldier(u32);
+    impl Unit for Soldier {
+        fn health(&self) -> Health {
+            Health(self.0)
+        }
+        fn hit(&mut self, damage: u32) -> bool {
+            self.0 = self.0.saturating_sub(damage);
+            self.0 > 0
+        }
+        fn label(&self) -> &'static str {
+            "soldier"
+        }
+    }
+    struct Wall;
+    impl Unit for Wall {
+        fn health(&self) -> Health {
+            Health(u32::MAX)
+        }
+        fn hit(&mut self, _damage: u32) -> bool {
+            true
+        }
+    }
+
+    TraitHList! { UnitHList for trait Unit use export_fixture::__unit_sigs; }
+    TraitHList! {
+        LabelsHList for trait export_fixture::Unit use export_fixture::__unit_sigs {
+            #[name = labels]
+            fn label;
+        }
+    }
+
+    let mut list = hlist![Soldier(3), Wall];
+    assert_eq!(list.hit(2), hlist![true, true]);
+    assert!(!list.all_hit(2));
+    let hlist![soldier, wall] = list.health();
+    assert_eq!((soldier.0, wall.0), (0, u32::MAX));
+    assert_eq!(list.label(), hlist!["soldier", "unit"]);
+    assert_eq!(list.labels_at_index(1), "unit");
+}
+
+/// A component with a fixed state, for the companions of traits lifted with `__component_sigs`.
+struct Fixed(bool, &'static str);
+impl Component for Fixed {
+    fn update(&mut self, _dt: f32) -> bool {
+        self.0
+    }
+    fn name(&self) -> &'static str {
+        self.1
+    }
+}
+
+#[test]
+fn signature_mismatch_message() {
+    trybuild::TestCases::new().compile_fail("tests/ui/signature_mismatch.rs");
+}
+
+#[test]
+fn hygienic_names() {
+    pub trait Shift {
+        fn shift(&self, __hlist_head: i32, __hlist_tail: i32, __hlist_index: i32) -> i32;
+    }
+    impl Shift for i32 {
+        fn shift(&self, __hlist_head: i32, __hlist_tail: i32, __hlist_index: i32) -> i32 {
+            self + __hlist_head * 100 + __hlist_tail * 10 + __hlist_index
+        }
+    }
+
+    TraitHList! {
+        pub ShiftHList for trait Shift {
+            fn shift(&self, __hlist_head: i32, __hlist_tail: i32, __hlist_index: i32) -> i32;
+        }
+    }
+
+    let list = hlist![1000, 2000];
+    assert_eq!(list.shift(1, 2, 3), hlist![1123, 2123]);
+    assert_eq!(list.shift_at_index(1, 2, 3, 1), 2123);
+}
+
+#[test]
+fn numeric_reductions() {
+    pub trait Measure {
+        fn weight(&self) -> u8;
+        fn volume(&self, scale: f64) -> f64;
+    }
+    struct Crate(u8);
+    impl Measure for Crate {
+        fn weight(&self) -> u8 {
+            self.0
+        }
+        fn volume(&self, scale: f64) -> f64 {
+            self.0 as f64 * scale
+        }
+    }
+    impl Measure for f64 {
+        fn weight(&self) -> u8 {
+            0
+        }
+        fn volume(&self, scale: f64) -> f64 {
+            self * scale
+        }
+    }
+    struct Barrel;
+    impl Measure for Barrel {
+        fn weight(&self) -> u8 {
+            200
+        }
+        fn volume(&self, scale: f64) -> f64 {
+            4.0 * scale
+        }
+    }
+
+    TraitHList! {
+        pub MeasureHList for trait Measure {
+            #[hlist(sum, product, checked_sum)]
+            fn weight(&self) -> u8;
+            #[hlist(mean, product)]
+            fn volume(&self, scale: f64) -> f64;
+        }
+    }
+
+    let small = hlist![Crate(2), Crate(3)];
+    assert_eq!(small.sum_weight(), 5);
+    assert_eq!(small.product_weight(), 6);
+    assert_eq!(small.checked_sum_weight(), Some(5));
+    assert_eq!(small.weight(), hlist![2, 3]);
+    assert_eq!(small.weight_at_index(1), 3);
+
+    let heavy = hlist![Crate(100), Barrel];
+    assert_eq!(heavy.checked_sum_weight(), None);
+
+    assert_eq!(heavy.sum_volume(0.5), 52.0);
+    assert_eq!(heavy.product_volume(0.5), 100.0);
+    assert_eq!(heavy.mean_volume(0.5), 26.0);
+    assert!(hlist2::Nil.mean_volume(0.5).is_nan());
+
+    let uneven = hlist![1e16, 1.0, 1.0];
+    assert_eq!(uneven.sum_volume(1.0), [1e16, 1.0, 1.0].iter().sum::<f64>());
+    assert_ne!(uneven.sum_volume(1.0), 1e16 + (1.0 + 1.0));
+    assert_eq!(heavy.volume(1.0), hlist![100.0, 4.0]);
+    assert_eq!(heavy.volume_at_index(1.0, 0), 100.0);
+
+    assert_eq!(hlist2::Nil.sum_weight(), 0);
+    assert_eq!(hlist2::Nil.product_weight(), 1);
+    assert_eq!(hlist2::Nil.checked_sum_weight(), Some(0));
+}
+
+#[test]
+fn numeric_reductions_with_owned_arguments() {
+    pub trait Score {
+        fn score(&self, unit: String) -> u32;
+    }
+    impl Score for u32 {
+        fn score(&self, unit: String) -> u32 {
+            self * unit.len() as u32
+        }
+    }
+
+    TraitHList! {
+        pub ScoreHList for trait Score {
+            #[hlist(sum, product, checked_sum)]
+            fn score(&self, unit: String) -> u32;
+        }
+    }
+
+    let list = hlist![2u32, 3u32, 4u32];
+    assert_eq!(list.sum_score(String::from("ab")), 18);
+    assert_eq!(list.product_score(String::from("ab")), 192);
+    assert_eq!(list.checked_sum_score(String::from("ab")), Some(18));
+}
+
+#[test]
+fn ordering_reductions() {
+    use std::cmp::Ordering;
+
+    pub trait Task {
+        fn priority(&self) -> u8;
+        fn label(&self) -> &'static str;
+        fn compare(&self, other: u8) -> Ordering;
+    }
+    struct Job(u8, &'static str);
+    impl Task for Job {
+        fn priority(&self) -> u8 {
+            self.0
+        }
+        fn label(&self) -> &'static str {
+            self.1
+        }
+        fn compare(&self, other: u8) -> Ordering {
+            self.0.cmp(&other)
+        }
+    }
+    struct Idle;
+    impl Task for Idle {
+        fn priority(&self) -> u8 {
+            0
+        }
+        fn label(&self) -> &'static str {
+            "idle"
+        }
+        fn compare(&self, _other: u8) -> Ordering {
+            Ordering::Less
+        }
+    }
+
+    TraitHList! {
+        pub TaskHList for trait Task {
+            #[hlist(max, min, argmax, argmin, is_sorted)]
+            fn priority(&self) -> u8;
+            #[hlist(max, argmin, is_sorted)]
+            fn label(&self) -> &'static str;
+            fn compare(&self, other: u8) -> Ordering;
+        }
+    }
+
+    let tasks = hlist![Job(3, "build"), Idle, Job(7, "test"), Job(7, "deploy"), Idle];
+    assert_eq!(tasks.priority(), hlist![3, 0, 7, 7, 0]);
+    assert_eq!(tasks.priority_at_index(2), 7);
+    assert_eq!(tasks.max_priority(), Some(7));
+    assert_eq!(tasks.min_priority(), Some(0));
+    assert_eq!(tasks.argmax_priority(), Some(2));
+    assert_eq!(tasks.argmin_priority(), Some(1));
+    assert!(!tasks.is_sorted_priority());
+    assert!(hlist![Idle, Job(1, "a"), Job(1, "b")].is_sorted_priority());
+
+    assert_eq!(tasks.label().0, "build");
+    assert_eq!(tasks.label_at_index(1), "idle");
+    assert_eq!(tasks.max_label(), Some("test"));
+    assert_eq!(tasks.argmin_label(), Some(0));
+    assert!(hlist![Job(0, "a"), Job(0, "b")].is_sorted_label());
+
+    assert_eq!(tasks.compare(3).0, Ordering::Equal);
+    assert_eq!(tasks.compare_at_index(3, 2), Ordering::Greater);
+    assert_eq!(hlist![Job(3, "a"), Job(5, "b")].cmp_compare(3), Ordering::Greater);
+    assert_eq!(hlist![Job(3, "a"), Idle].cmp_compare(3), Ordering::Less);
+    assert_eq!(hlist![Job(3, "a"), Job(3, "b")].cmp_compare(3), Ordering::Equal);
+
+    assert_eq!(hlist2::Nil.max_priority(), None);
+    assert_eq!(hlist2::Nil.argmin_priority(), None);
+    assert!(hlist2::Nil.is_sorted_priority());
+}
+
+#[test]
+fn bool_aggregates() {
+    pub trait Matches {
+        fn matches(&self, pattern: String) -> bool;
+    }
+    impl Matches for &str {
+        fn matches(&self, pattern: String) -> bool {
+            self.contains(&pattern)
+        }
+    }
+    impl Matches for char {
+        fn matches(&self, pattern: String) -> bool {
+            pattern.starts_with(*self)
+        }
+    }
+
+    TraitHList! {
+        pub MatchesHList for trait Matches {
+            fn matches(&self, pattern: String) -> bool;
+        }
+    }
+
+    let list = hlist!["needle", 'n', "haystack", 'x'];
+    let pattern = || String::from("ne");
+    assert_eq!(list.matches(pattern()), hlist![true, true, false, false]);
+    assert!(list.matches_at_index(pattern(), 1));
+    assert!(list.any_matches(pattern()));
+    assert!(!list.all_matches(pattern()));
+    assert_eq!(list.count_matches(pattern()), 2);
+    assert!(!list.none_matches(pattern()));
+    assert!(!list.exactly_one_matches(pattern()));
+    assert_eq!(list.mask_matches(pattern()), 0b0011);
+
+    assert!(list.exactly_one_matches(String::from("hay")));
+    assert_eq!(list.mask_matches(String::from("hay")), 0b0100);
+    assert!(list.none_matches(String::from("zzz")));
+    assert_eq!(list.count_matches(String::from("zzz")), 0);
+
+    assert_eq!(hlist2::Nil.count_matches(pattern()), 0);
+    assert_eq!(hlist2::Nil.mask_matches(pattern()), 0);
+    assert!(hlist2::Nil.none_matches(pattern()));
+}
+
+#[test]
+fn bool_aggregates_on_exported_signatures() {
+    TraitHList! { UpdateHList for trait Component use __component_sigs; }
+
+    let mut list = hlist![Fixed(false, "timer"), Fixed(true, "counter")];
+    assert_eq!(list.count_update(0.5), 1);
+    assert!(list.exactly_one_update(0.5));
+    assert!(!list.none_update(0.5));
+    assert_eq!(list.mask_update(0.5), 0b10);
+}
+
+#[test]
+fn search_companions() {
+    pub trait Handler {
+        fn accepts(&self, event: &str) -> bool;
+        fn handle(&mut self, event: String) -> Option<String>;
+    }
+    struct Prefix(&'static str, usize);
+    impl Handler for Prefix {
+        fn accepts(&self, event: &str) -> bool {
+            event.starts_with(self.0)
+        }
+        fn handle(&mut self, event: String) -> Option<String> {
+            self.1 += 1;
+            event.strip_prefix(self.0).map(str::to_uppercase)
+        }
+    }
+
+    TraitHList! {
+        pub HandlerHList for trait Handler {
+            fn accepts(&self, event: &str) -> bool;
+            fn handle(&mut self, event: String) -> Option<String>;
+        }
+    }
+
+    let mut handlers = hlist![Prefix("key:", 0), Prefix("mouse:", 0), Prefix("key:", 0)];
+    assert_eq!(handlers.accepts("key:a"), hlist![true, false, true]);
+    assert!(handlers.accepts_at_index("mouse:left", 1));
+    assert_eq!(handlers.position_accepts("key:a"), Some(0));
+    assert_eq!(handlers.rposition_accepts("key:a"), Some(2));
+    assert_eq!(handlers.position_accepts("mouse:left"), Some(1));
+    assert_eq!(handlers.rposition_accepts("mouse:left"), Some(1));
+    assert_eq!(handlers.position_accepts("touch"), None);
+    assert_eq!(handlers.rposition_accepts("touch"), None);
+
+    assert_eq!(handlers.find_map_handle("mouse:left".into()), Some("LEFT".into()));
+    let hlist2::Cons(first, hlist2::Cons(second, hlist2::Cons(third, _))) = &handlers;
+    assert_eq!((first.1, second.1, third.1), (1, 1, 0));
+
+    assert_eq!(handlers.find_map_handle("touch".into()), None);
+    assert_eq!(handlers.handle("key:a".into()), hlist![Some("A".into()), None, Some("A".into())]);
+    assert_eq!(handlers.handle_at_index("key:b".into(), 2), Some("B".into()));
+    assert_eq!(handlers.count_accepts("key:a"), 2);
+    assert!(!handlers.none_accepts("key:a"));
+    assert!(!handlers.exactly_one_accepts("key:a"));
+    assert_eq!(handlers.mask_accepts("key:a"), 0b101);
+    assert!(handlers.any_accepts("key:a") && !handlers.all_accepts("key:a"));
+}
+
+#[test]
+fn search_companions_on_exported_signatures() {
+    TraitHList! { UpdateHList for trait Component use __component_sigs; }
+
+    let mut list = hlist![Fixed(false, "timer"), Fixed(true, "counter")];
+    assert_eq!(list.position_update(0.5), Some(1));
+    assert_eq!(list.rposition_update(0.5), Some(1));
+}
+
+#[test]
+fn fallible_companions() {
+    #[derive(Debug, PartialEq)]
+    pub struct RangeError(u32);
+    #[derive(Debug, PartialEq)]
+    pub enum ConfigError {
+        Range(u32),
+    }
+    impl From<RangeError> for ConfigError {
+        fn from(RangeError(value): RangeError) -> Self {
+            ConfigError::Range(value)
+        }
+    }
+
+    pub trait Setting: Sized {
+        fn parse(&self, limit: u32) -> Result<u32, RangeError>;
+        fn require(&self) -> Result<Self, &'static str>;
+        fn fallback(&self) -> Option<Self>;
+    }
+    #[derive(Debug, PartialEq, Clone, Copy)]
+    struct Number(u32);
+    impl Setting for Number {
+        fn parse(&self, limit: u32) -> Result<u32, RangeError> {
+            if self.0 <= limit { Ok(self.0) } else { Err(RangeError(self.0)) }
+        }
+        fn require(&self) -> Result<Self, &'static str> {
+            Ok(*self)
+        }
+        fn fallback(&self) -> Option<Self> {
+            (self.0 > 0).then_some(*self)
+        }
+    }
+    #[derive(Debug, PartialEq, Clone, Copy)]
+    struct Flag(Option<bool>);
+    impl Setting for Flag {
+        fn parse(&self, _limit: u32) -> Result<u32, RangeError> {
+            Ok(self.0.unwrap_or_default() as u32)
+        }
+        fn require(&self) -> Result<Self, &'static str> {
+            self.0.map(|_| *self).ok_or("flag")
+        }
+        fn fallback(&self) -> Option<Self> {
+            self.0.map(|_| *self)
+        }
+    }
+
+    TraitHList! {
+        pub SettingHList for trait Setting {
+            #[hlist(error = ConfigError)]
+            fn parse(&self, limit: u32) -> Result<u32, RangeError>;
+            fn require(&self) -> Result<Self, &'static str>;
+            fn fallback(&self) -> Option<Self>;
+        }
+    }
+
+    let good = hlist![Number(3), Flag(Some(true)), Number(5)];
+    assert_eq!(good.try_parse(10), Ok(hlist![3, 1, 5]));
+    assert_eq!(good.try_parse(4), Err(ConfigError::Range(5)));
+    assert_eq!(good.collect_errors_parse(10), vec![]);
+    assert_eq!(good.collect_errors_parse(2), vec![(0, ConfigError::Range(3)), (2, ConfigError::Range(5))]);
+    assert_eq!(good.parse(4), hlist![Ok(3), Ok(1), Err(RangeError(5))]);
+    assert_eq!(good.parse_at_index(4, 0), Ok(3));
+
+    assert_eq!(good.try_require(), Ok(hlist![Number(3), Flag(Some(true)), Number(5)]));
+    let bad = hlist![Number(0), Flag(None)];
+    assert_eq!(bad.try_require(), Err("flag"));
+    assert_eq!(bad.collect_errors_require(), vec![(1, "flag")]);
+    assert_eq!(bad.require(), hlist![Ok(Number(0)), Err("flag")]);
+
+    assert_eq!(good.all_some_fallback(), Some(good));
+    assert_eq!(bad.all_some_fallback(), None);
+    assert_eq!(bad.fallback(), hlist![None, None]);
+    assert_eq!(hlist2::Nil.all_some_fallback(), Some(hlist2::Nil));
+    assert_eq!(hlist2::Nil.collect_errors_parse(0), vec![]);
+}
+
+#[test]
+fn fold_and_scan() {
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct Vec2(i32, i32);
+    impl std::ops::Add for Vec2 {
+        type Output = Vec2;
+        fn add(self, other: Vec2) -> Vec2 {
+            Vec2(self.0 + other.0, self.1 + other.1)
+        }
+    }
+
+    pub trait Body {
+        fn offset(&self, scale: i32) -> Vec2;
+    }
+    struct Point(i32, i32);
+    impl Body for Point {
+        fn offset(&self, scale: i32) -> Vec2 {
+            Vec2(self.0 * scale, self.1 * scale)
+        }
+    }
+    struct Origin;
+    impl Body for Origin {
+        fn offset(&self, _scale: i32) -> Vec2 {
+            Vec2(0, 0)
+        }
+    }
+
+    TraitHList! {
+        pub BodyHList for trait Body {
+            fn offset(&self, scale: i32) -> Vec2;
+        }
+    }
+
+    let bodies = hlist![Point(1, 2), Origin, Point(3, -1)];
+    assert_eq!(bodies.offset(2), hlist![Vec2(2, 4), Vec2(0, 0), Vec2(6, -2)]);
+    assert_eq!(bodies.offset_at_index(2, 0), Vec2(2, 4));
+    assert_eq!(bodies.fold_offset(2, Vec2(0, 0), |acc, offset| acc + offset), Vec2(8, 2));
+    assert_eq!(
+        bodies.scan_offset(1, Vec2(0, 0), |acc, offset| acc + offset),
+        hlist![Vec2(1, 2), Vec2(1, 2), Vec2(4, 1)]
+    );
+    assert_eq!(
+        bodies.scan_offset(1, i32::MIN, |max, offset| max.max(offset.0)),
+        hlist![1, 1, 3]
+    );
+
+    let mut visited = vec![];
+    let count = bodies.fold_offset(1, 0, |count, offset| {
+        visited.push(offset);
+        count + 1
+    });
+    assert_eq!(count, 3);
+    assert_eq!(visited, [Vec2(1, 2), Vec2(0, 0), Vec2(3, -1)]);
+    assert_eq!(hlist2::Nil.scan_offset(1, 0, |acc: i32, _| acc), hlist2::Nil);
+}
+
+#[test]
+fn fold_and_scan_on_exported_signatures() {
+    TraitHList! { ComponentHList for trait Component use __component_sigs; }
+    TraitHList! {
+        NamesHList for trait Component use __component_sigs {
+            #[name = names]
+            fn name;
+        }
+    }
+
+    let mut list = hlist![Fixed(false, "timer"), Fixed(true, "counter")];
+    assert_eq!(list.fold_update(0.5, 0, |count, alive| count + alive as u32), 1);
+    assert_eq!(list.scan_update(0.5, false, |any, alive| any || alive), hlist![false, true]);
+    assert_eq!(list.fold_name(String::new(), |names, name| names + name), "timercounter");
+    assert_eq!(list.scan_name(0, |len, name| len + name.len()), hlist![5, 12]);
+    assert_eq!(list.fold_names(0, |count, _| count + 1), 2);
+    assert_eq!(list.scan_names((), |(), _| ()), hlist![(), ()]);
+}
+
+#[test]
+fn collect_uniform_outputs() {
+    pub trait Sensor {
+        fn read(&self, offset: i32) -> i32;
+        fn reset(&mut self, to: i32) -> i32;
+        fn unit() -> &'static str;
+        fn take(self) -> i32;
+    }
+    struct Thermometer(i32);
+    impl Sensor for Thermometer {
+        fn read(&self, offset: i32) -> i32 {
+            self.0 + offset
+        }
+        fn reset(&mut self, to: i32) -> i32 {
+            std::mem::replace(&mut self.0, to)
+        }
+        fn unit() -> &'static str {
+            "°C"
+        }
+        fn take(self) -> i32 {
+            self.0
+        }
+    }
+    struct Hygrometer(i32);
+    impl Sensor for Hygrometer {
+        fn read(&self, offset: i32) -> i32 {
+            self.0 * 2 + offset
+        }
+        fn reset(&mut self, to: i32) -> i32 {
+            std::mem::replace(&mut self.0, to)
+        }
+        fn unit() -> &'static str {
+            "%"
+        }
+        fn take(self) -> i32 {
+            self.0
+        }
+    }
+
+    TraitHList! {
+        pub SensorHList for trait Sensor {
+            #[hlist(vec)]
+            fn read(&self, offset: i32) -> i32;
+            #[hlist(vec)]
+            fn reset(&mut self, to: i32) -> i32;
+            #[hlist(vec)]
+            fn unit() -> &'static str;
+            #[hlist(vec)]
+            fn take(self) -> i32;
+        }
+    }
+
+    type Sensors = HList![Thermometer, Hygrometer, Thermometer];
+    assert_eq!(<Sensors as SensorHList>::LEN, 3);
+    assert_eq!(<hlist2::Nil as SensorHList>::LEN, 0);
+
+    let mut sensors: Sensors = hlist![Thermometer(20), Hygrometer(30), Thermometer(25)];
+    assert_eq!(sensors.read(1), hlist![21, 61, 26]);
+    assert_eq!(sensors.read_vec(1), vec![21, 61, 26]);
+    assert_eq!(sensors.read_array(0), [20, 60, 25]);
+    let [first, ..]: [i32; 3] = sensors.read_array(5);
+    assert_eq!(first, 25);
+    assert_eq!(sensors.read_iter(0).max(), Some(60));
+    assert_eq!(sensors.read_iter(0).skip(1).collect::<Vec<_>>(), vec![60, 25]);
+    assert_eq!(sensors.read_at_index(0, 2), 25);
+
+    let mut resets = sensors.reset_iter(0);
+    assert_eq!(resets.next(), Some(20));
+    assert_eq!(resets.next(), Some(30));
+    drop(resets);
+    assert_eq!(sensors.reset_vec(1), vec![0, 0, 25]);
+    assert_eq!(sensors.reset_array(2), [1, 1, 1]);
+    assert_eq!(sensors.reset(3), hlist![2, 2, 2]);
+    assert_eq!(sensors.reset_at_index(4, 1), 3);
+
+    assert_eq!(Sensors::unit_array(), ["°C", "%", "°C"]);
+    assert_eq!(Sensors::unit_iter().collect::<String>(), "°C%°C");
+    assert_eq!(Sensors::unit_vec().len(), 3);
+    assert_eq!(Sensors::unit(), hlist!["°C", "%", "°C"]);
+    assert_eq!(Sensors::unit_at_index(1), "%");
+
+    assert_eq!(sensors.take_vec(), vec![3, 4, 3]);
+    let sensors: Sensors = hlist![Thermometer(1), Hygrometer(2), Thermometer(3)];
+    assert_eq!(sensors.take_array(), [1, 2, 3]);
+    let sensors: Sensors = hlist![Thermometer(1), Hygrometer(2), Thermometer(3)];
+    assert_eq!(sensors.take(), hlist![1, 2, 3]);
+    assert_eq!(hlist![Thermometer(4)].take_at_index(0), 4);
+}
+
+#[test]
+fn collect_uniform_outputs_on_exported_signatures() {
+    TraitHList! {
+        ComponentHList for trait Component use __component_sigs {
+            #[hlist(vec)]
+            fn update;
+            #[hlist(vec)]
+            fn name;
+        }
+    }
+    TraitHList! {
+        NamesHList for trait Component use __component_sigs {
+            #[name = names]
+            #[hlist(vec)]
+            fn name;
+        }
+    }
+
+    let mut list = hlist![Fixed(false, "timer"), Fixed(true, "counter")];
+    assert_eq!(list.update_vec(0.5), vec![false, true]);
+    assert_eq!(list.update_array::<2>(0.5), [false, true]);
+    assert_eq!(list.update_iter(0.5).collect::<Vec<_>>(), vec![false, true]);
+    assert_eq!(list.name_vec(), vec!["timer", "counter"]);
+    assert_eq!(list.names_vec(), list.name_vec());
+    assert_eq!(list.name_array::<2>(), list.names_array::<2>());
+    assert_eq!(list.name_iter().chain(list.names_iter()).count(), 4);
+}
+
+#[test]
+fn vec_is_opt_in() {
+    pub trait Ids {
+        fn id(&self) -> u32;
+        fn id_vec(&self) -> Vec<u32>;
+    }
+    impl Ids for u32 {
+        fn id(&self) -> u32 {
+            *self
+        }
+        fn id_vec(&self) -> Vec<u32> {
+            vec![*self; 2]
+        }
+    }
+
+    TraitHList! {
+        pub IdsHList for trait Ids {
+            fn id(&self) -> u32;
+            fn id_vec(&self) -> Vec<u32>;
+        }
+    }
+
+    let list = hlist![1u32, 2u32];
+    assert_eq!(list.id(), hlist![1, 2]);
+    assert_eq!(list.id_vec(), hlist![vec![1, 1], vec![2, 2]]);
+}
+
+#[test]
+fn joined_outputs() {
+    use std::fmt::Write;
+
+    pub trait Device {
+        fn describe(&self) -> String;
+        fn label(&self) -> &str;
+        fn temperature(&self) -> f32;
+    }
+    struct Lamp {
+        name: String,
+        on: bool,
+    }
+    impl Device for Lamp {
+        fn describe(&self) -> String {
+            format!("{}: {}", self.name, if self.on { "on" } else { "off" })
+        }
+        fn label(&self) -> &str {
+            &self.name
+        }
+        fn temperature(&self) -> f32 {
+            if self.on { 40.5 } else { 20.0 }
+        }
+    }
+    struct Fan(u8);
+    impl Device for Fan {
+        fn describe(&self) -> String {
+            format!("fan: speed {}", self.0)
+        }
+        fn label(&self) -> &str {
+            "fan"
+        }
+        fn temperature(&self) -> f32 {
+            18.25
+        }
+    }
+
+    TraitHList! {
+        pub DeviceHList for trait Device {
+            fn describe(&self) -> String;
+            #[hlist(vec)]
+            fn label(&self) -> &str;
+            #[hlist(join)]
+            fn temperature(&self) -> f32;
+        }
+    }
+
+    let devices = hlist![Lamp { name: "desk".into(), on: true }, Fan(3), Lamp { name: "hall".into(), on: false }];
+    assert_eq!(devices.join_describe(", "), "desk: on, fan: speed 3, hall: off");
+    assert_eq!(devices.join_label("|"), "desk|fan|hall");
+    assert_eq!(devices.join_temperature(" / "), "40.5 / 18.25 / 20");
+
+    let mut status = String::from("devices: ");
+    devices.write_label(&mut status, ", ").unwrap();
+    writeln!(status).unwrap();
+    assert_eq!(status, "devices: desk, fan, hall\n");
+    let writer: &mut dyn Write = &mut status;
+    devices.write_temperature(writer, ";").unwrap();
+    assert_eq!(status, "devices: desk, fan, hall\n40.5;18.25;20");
+
+    assert_eq!(devices.label(), hlist!["desk", "fan", "hall"]);
+    assert_eq!(devices.label_at_index(1), "fan");
+    assert_eq!(devices.label_vec(), vec!["desk", "fan", "hall"]);
+    assert_eq!(devices.describe().1.0, "fan: speed 3");
+    assert_eq!(devices.temperature_iter().sum::<f32>(), 78.75);
+    assert_eq!(hlist2::Nil.join_label(", "), "");
+}
+
+#[test]
+fn joined_outputs_on_exported_signatures() {
+    TraitHList! { ComponentHList for trait Component use __component_sigs; }
+    TraitHList! {
+        NamesHList for trait Component use __component_sigs {
+            #[name = names]
+            fn name;
+        }
+    }
+
+    let list = hlist![Fixed(false, "timer"), Fixed(true, "counter")];
+    assert_eq!(list.join_name(", "), list.join_names(", "));
+    let mut names = String::new();
+    list.write_names(&mut names, "+").unwrap();
+    assert_eq!(names, "timer+counter");
+}
+
+#[test]
+fn unzip_tuples() {
+    #[derive(Debug, PartialEq, Clone, Copy)]
+    pub struct Pos(i32);
+    #[derive(Debug, PartialEq, Clone, Copy)]
+    pub struct Vel(i32);
+
+    pub trait Entity: Sized {
+        fn split(&self) -> (Pos, Vel);
+        fn parts(self, tag: char) -> (Self, char, usize);
+    }
+    #[derive(Debug, PartialEq)]
+    struct Ship(i32);
+    impl Entity for Ship {
+        fn split(&self) -> (Pos, Vel) {
+            (Pos(self.0), Vel(self.0 * 2))
+        }
+        fn parts(self, tag: char) -> (Self, char, usize) {
+            (self, tag, 1)
+        }
+    }
+    #[derive(Debug, PartialEq)]
+    struct Rock;
+    impl Entity for Rock {
+        fn split(&self) -> (Pos, Vel) {
+            (Pos(-1), Vel(0))
+        }
+        fn parts(self, tag: char) -> (Self, char, usize) {
+            (self, tag.to_ascii_uppercase(), 2)
+        }
+    }
+
+    TraitHList! {
+        pub EntityHList for trait Entity {
+            #[hlist(vec)]
+            fn split(&self) -> (Pos, Vel);
+            fn parts(self, tag: char) -> (Self, char, usize);
+        }
+    }
+
+    let entities = hlist![Ship(1), Rock, Ship(5)];
+    assert_eq!(
+        entities.unzip_split(),
+        (hlist![Pos(1), Pos(-1), Pos(5)], hlist![Vel(2), Vel(0), Vel(10)])
+    );
+    assert_eq!(entities.split().0, (Pos(1), Vel(2)));
+    assert_eq!(entities.split_at_index(1), (Pos(-1), Vel(0)));
+    assert_eq!(entities.split_vec().len(), 3);
+    assert_eq!(entities.fold_split(0, |sum, (pos, _)| sum + pos.0), 5);
+
+    let (ships, tags, sizes) = entities.unzip_parts('r');
+    assert_eq!(ships, hlist![Ship(1), Rock, Ship(5)]);
+    assert_eq!(tags, hlist!['r', 'R', 'r']);
+    assert_eq!(sizes, hlist![1, 2, 1]);
+    assert_eq!(ships.parts('x').1.0, (Rock, 'X', 2));
+    assert_eq!(hlist2::Nil.unzip_split(), (hlist2::Nil, hlist2::Nil));
+}
+
+#[test]
+fn flatten_outputs() {
+    use std::collections::BTreeSet;
+
+    pub trait Node: Sized {
+        fn children(&self) -> Vec<u32>;
+        fn tags(&self, min: u8) -> BTreeSet<u8>;
+        fn ports(&self) -> HList![u16, Self];
+    }
+    #[derive(Debug, PartialEq, Clone)]
+    struct Leaf(u32);
+    impl Node for Leaf {
+        fn children(&self) -> Vec<u32> {
+            vec![]
+        }
+        fn tags(&self, min: u8) -> BTreeSet<u8> {
+            [3, 1].into_iter().filter(|tag| *tag >= min).collect()
+        }
+        fn ports(&self) -> HList![u16, Self] {
+            hlist![80, self.clone()]
+        }
+    }
+    #[derive(Debug, PartialEq, Clone)]
+    struct Branch(u32, u32);
+    impl Node for Branch {
+        fn children(&self) -> Vec<u32> {
+            vec![self.0, self.1]
+        }
+        fn tags(&self, min: u8) -> BTreeSet<u8> {
+            [7].into_iter().filter(|tag| *tag >= min).collect()
+        }
+        fn ports(&self) -> HList![u16, Self] {
+            hlist![443, self.clone()]
+        }
+    }
+
+    TraitHList! {
+        pub NodeHList for trait Node {
+            #[hlist(vec)]
+            fn children(&self) -> Vec<u32>;
+            #[hlist(flat)]
+            fn tags(&self, min: u8) -> BTreeSet<u8>;
+            #[hlist(concat)]
+            fn ports(&self) -> HList![u16, Self];
+        }
+    }
+
+    let nodes = hlist![Branch(1, 2), Leaf(3), Branch(4, 5)];
+    assert_eq!(nodes.flat_children(), vec![1, 2, 4, 5]);
+    assert_eq!(nodes.flat_children_iter().collect::<Vec<_>>(), vec![1, 2, 4, 5]);
+    assert_eq!(nodes.flat_children_iter().nth(2), Some(4));
+    assert_eq!(nodes.children().1.0, Vec::<u32>::new());
+    assert_eq!(nodes.children_at_index(2), vec![4, 5]);
+    assert_eq!(nodes.children_vec().len(), 3);
+
+    assert_eq!(nodes.flat_tags(2), vec![7, 3, 7]);
+    assert_eq!(nodes.flat_tags_iter(0).sum::<u8>(), 18);
+    assert_eq!(nodes.tags_at_index(0, 1), BTreeSet::from([3, 1]));
+
+    assert_eq!(
+        nodes.concat_ports(),
+        hlist![443, Branch(1, 2), 80, Leaf(3), 443, Branch(4, 5)]
+    );
+    assert_eq!(nodes.ports().0, hlist![443, Branch(1, 2)]);
+    assert_eq!(hlist2::Nil.concat_ports(), hlist2::Nil);
+    assert_eq!(hlist2::Nil.flat_children(), Vec::<u32>::new());
+}
+
+#[test]
+fn chained_mutators() {
+    use std::cell::Cell;
+
+    pub trait Clock {
+        fn reset(&mut self);
+        fn tick(&mut self, dt: f32);
+        fn log(&self, lines: &Cell<u32>);
+        fn elapsed(&self) -> f32;
+    }
+    struct Stopwatch(f32);
+    impl Clock for Stopwatch {
+        fn reset(&mut self) {
+            self.0 = 0.0;
+        }
+        fn tick(&mut self, dt: f32) {
+            self.0 += dt;
+        }
+        fn log(&self, lines: &Cell<u32>) {
+            lines.set(lines.get() + 1);
+        }
+        fn elapsed(&self) -> f32 {
+            self.0
+        }
+    }
+    struct Countdown(f32);
+    impl Clock for Countdown {
+        fn reset(&mut self) {
+            self.0 = 10.0;
+        }
+        fn tick(&mut self, dt: f32) {
+            self.0 -= dt;
+        }
+        fn log(&self, lines: &Cell<u32>) {
+            lines.set(lines.get() + 10);
+        }
+        fn elapsed(&self) -> f32 {
+            10.0 - self.0
+        }
+    }
+
+    TraitHList! {
+        pub ClockHList for trait Clock {
+            #[hlist(chain)]
+            fn reset(&mut self);
+            #[hlist(chain)]
+            fn tick(&mut self, dt: f32);
+            #[hlist(chain)]
+            fn log(&self, lines: &Cell<u32>);
+            #[hlist(vec)]
+            fn elapsed(&self) -> f32;
+        }
+    }
+
+    let mut clocks = hlist![Stopwatch(3.0), Countdown(1.0)];
+    assert_eq!(clocks.reset().tick(0.5).tick(0.25).elapsed(), hlist![0.75, 0.75]);
+    clocks.tick_at_index(1.0, 0);
+    clocks.reset_at_index(1);
+    assert_eq!(clocks.elapsed(), hlist![1.75, 0.0]);
+
+    let lines = Cell::new(0);
+    clocks.log(&lines).log(&lines);
+    clocks.log_at_index(&lines, 1);
+    assert_eq!(lines.get(), 32);
+    assert_eq!(clocks.elapsed_vec(), vec![1.75, 0.0]);
+}
+
+#[test]
+fn builder_transformations() {
+    pub trait Channel: Sized {
+        fn with_gain(self, gain: f32) -> Self;
+        fn with_offset(self, offset: f32) -> Self;
+        fn level(&self) -> f32;
+        fn muted() -> Self;
+    }
+    #[derive(Debug, PartialEq)]
+    struct Mono(f32);
+    impl Channel for Mono {
+        fn with_gain(self, gain: f32) -> Self {
+            Mono(self.0 * gain)
+        }
+        fn with_offset(self, offset: f32) -> Self {
+            Mono(self.0 + offset)
+        }
+        fn level(&self) -> f32 {
+            self.0
+        }
+        fn muted() -> Self {
+            Mono(0.0)
+        }
+    }
+    #[derive(Debug, PartialEq)]
+    struct Stereo(f32, f32);
+    impl Channel for Stereo {
+        fn with_gain(self, gain: f32) -> Self {
+            Stereo(self.0 * gain, self.1 * gain)
+        }
+        fn with_offset(self, offset: f32) -> Self {
+            Stereo(self.0 + offset, self.1 + offset)
+        }
+        fn level(&self) -> f32 {
+            self.0.max(self.1)
+        }
+        fn muted() -> Self {
+            Stereo(0.0, 0.0)
+        }
+    }
+
+    TraitHList! {
+        pub ChannelHList for trait Channel {
+            fn with_gain(self, gain: f32) -> Self;
+            fn with_offset(self, offset: f32) -> Self;
+            fn level(&self) -> f32;
+            fn muted() -> Self;
+        }
+    }
+
+    fn configure<L: ChannelHList>(list: L) -> L {
+        list.with_gain(0.5).with_offset(1.0)
+    }
+
+    type Mixer = HList![Mono, Stereo, Mono];
+    let mixer: Mixer = hlist![Mono(2.0), Stereo(4.0, 8.0), Mono(0.0)];
+    let mixer: Mixer = configure(mixer);
+    assert_eq!(mixer, hlist![Mono(2.0), Stereo(3.0, 5.0), Mono(1.0)]);
+    assert_eq!(mixer.level(), hlist![2.0, 5.0, 1.0]);
+    assert_eq!(Mixer::muted(), hlist![Mono(0.0), Stereo(0.0, 0.0), Mono(0.0)]);
+    assert_eq!(hlist2::Nil.with_gain(2.0), hlist2::Nil);
+}
+
+#[test]
+fn companion_selection() {
+    pub trait Sensor {
+        fn ready(&self) -> bool;
+        fn ready_at_index(&self) -> u8;
+        fn reading(&self) -> u32;
+        fn calibrated(&self) -> bool;
+    }
+    struct Thermometer(u32);
+    impl Sensor for Thermometer {
+        fn ready(&self) -> bool {
+            self.0 > 0
+        }
+        fn ready_at_index(&self) -> u8 {
+            0
+        }
+        fn reading(&self) -> u32 {
+            self.0
+        }
+        fn calibrated(&self) -> bool {
+            true
+        }
+    }
+
+    TraitHList! {
+        #[hlist(no_at_index)]
+        pub SensorHList for trait Sensor {
+            #[hlist(all = every_ready, no_mask)]
+            fn ready(&self) -> bool;
+            fn ready_at_index(&self) -> u8;
+            #[hlist(companions(at_index, vec, sum), vec = readings)]
+            fn reading(&self) -> u32;
+            #[hlist(companions(none))]
+            fn calibrated(&self) -> bool;
+        }
+    }
+
+    let sensors = hlist![Thermometer(3), Thermometer(0)];
+    assert!(!sensors.every_ready());
+    assert!(sensors.any_ready());
+    assert_eq!(sensors.count_ready(), 1);
+    assert_eq!(sensors.ready_at_index(), hlist![0, 0]);
+    assert_eq!(sensors.readings(), vec![3, 0]);
+    assert_eq!(sensors.sum_reading(), 3);
+    assert_eq!(sensors.reading_at_index(0), 3);
+    assert!(!sensors.none_calibrated());
+    assert!(sensors.any_calibrated());
+}
+
+#[test]
+fn header_error_type() {
+    #[derive(Debug, PartialEq)]
+    pub struct LoadError(&'static str);
+    impl From<&'static str> for LoadError {
+        fn from(message: &'static str) -> Self {
+            LoadError(message)
+        }
+    }
+
+    pub trait Asset {
+        fn load(&self) -> Result<u32, &'static str>;
+        fn size(&self) -> u32;
+    }
+    struct Texture(u32);
+    impl Asset for Texture {
+        fn load(&self) -> Result<u32, &'static str> {
+            if self.0 > 0 { Ok(self.0) } else { Err("empty texture") }
+        }
+        fn size(&self) -> u32 {
+            self.0
+        }
+    }
+
+    TraitHList! {
+        #[hlist(error = LoadError)]
+        pub AssetHList for trait Asset {
+            fn load(&self) -> Result<u32, &'static str>;
+            fn size(&self) -> u32;
+        }
+    }
+
+    let assets = hlist![Texture(4), Texture(0)];
+    assert_eq!(assets.try_load(), Err(LoadError("empty texture")));
+    assert_eq!(assets.collect_errors_load(), vec![(1, LoadError("empty texture"))]);
+    assert_eq!(assets.size(), hlist![4, 0]);
+}
+
+#[test]
+fn naming_templates() {
+    TraitHList! {
+        #[hlist(rename = "hlist_{}", at_index = "{}_at")]
+        pub CloneHList for trait Clone;
+    }
+    TraitHList! {
+        #[hlist(rename = "hlist_{}")]
+        pub DefaultHList for trait Default;
+    }
+
+    let list = hlist![1u8, String::from("two")];
+    assert_eq!(list.hlist_clone(), list);
+    assert_eq!(<HList![u8, String] as DefaultHList>::hlist_default(), hlist![0, String::new()]);
+
+    pub trait Door {
+        fn open(&self) -> bool;
+        fn width(&self) -> u32;
+    }
+    impl Door for u32 {
+        fn open(&self) -> bool {
+            *self > 0
+        }
+        fn width(&self) -> u32 {
+            *self
+        }
+    }
+
+    TraitHList! {
+        #[hlist(rename = "{}_each", all = "{}_all", any = "{}_any", at_index = "{}_at")]
+        pub DoorHList for trait Door {
+            #[hlist(all = all_open)]
+            fn open(&self) -> bool;
+            #[name = widths]
+            fn width(&self) -> u32;
+        }
+    }
+
+    let doors = hlist![80u32, 0u32];
+    assert_eq!(doors.open_each(), hlist![true, false]);
+    assert!(!doors.all_open());
+    assert!(doors.open_each_any());
+    assert!(!doors.open_each_at(1));
+    assert_eq!(doors.widths(), hlist![80, 0]);
+    assert_eq!(doors.widths_at(0), 80);
+}
+
+#[test]
+fn get_by_index() {
+    pub trait Label {
+        fn label(&self) -> String;
+    }
+    impl Label for u8 {
+        fn label(&self) -> String {
+            format!("u8 {self}")
+        }
+    }
+    impl Label for char {
+        fn label(&self) -> String {
+            format!("char {self}")
+        }
+    }
+
+    TraitHList! {
+        LabelHList for trait Label {
+            #[hlist(get)]
+            fn label(&self) -> String;
+        }
+    }
+
+    let widgets = hlist![1u8, 'x'];
+    assert_eq!(widgets.get_label(1), Some(String::from("char x")));
+    assert_eq!(widgets.get_label(2), None);
+    assert_eq!(hlist![].get_label(0), None);
+}
+
+#[test]
+fn get_by_index_on_exported_signatures() {
+    TraitHList! {
+        ComponentHList for trait Component use __component_sigs {
+            #[hlist(get)]
+            fn update;
+            #[hlist(get)]
+            fn name;
+        }
+    }
+    TraitHList! {
+        NamesHList for trait Component use __component_sigs {
+            #[name = names]
+            #[hlist(get)]
+            fn name;
+        }
+    }
+
+    let mut list = hlist![Fixed(false, "timer"), Fixed(true, "counter")];
+    assert_eq!(list.get_update(0.5, 2), None);
+    assert_eq!(list.get_name(1), Some("counter"));
+    assert_eq!(list.get_names(2), None);
+}
+
+#[test]
+fn get_is_opt_in() {
+    pub trait Lookup {
+        fn id(&self) -> u32;
+        fn get_id(&self, key: u32) -> bool;
+    }
+    impl Lookup for u32 {
+        fn id(&self) -> u32 {
+            *self
+        }
+        fn get_id(&self, key: u32) -> bool {
+            *self == key
+        }
+    }
+
+    TraitHList! {
+        pub LookupHList for trait Lookup {
+            fn id(&self) -> u32;
+            fn get_id(&self, key: u32) -> bool;
+        }
+    }
+
+    let list = hlist![1u32, 2u32];
+    assert_eq!(list.id_at_index(1), 2);
+    assert_eq!(list.get_id(2), hlist![false, true]);
+}
+
+#[test]
+#[should_panic(expected = "Index 2 is out of bounds for `label_at_index` on a list of length 2.")]
+fn at_index_out_of_bounds() {
+    pub trait Label {
+        fn label(&self) -> String;
+    }
+    impl Label for u8 {
+        fn label(&self) -> String {
+            format!("u8 {self}")
+        }
+    }
+
+    TraitHList! {
+        LabelHList for trait Label {
+            fn label(&self) -> String;
+        }
+    }
+
+    hlist![1u8, 2u8].label_at_index(2);
+}
+
+#[test]
+fn typed_index_access() {
+    use hlist2::ops::{Here, There};
+
+    pub trait Shape {
+        type Area;
+        fn area(&self) -> Self::Area;
+        fn scaled(&self, factor: u8) -> Self;
+    }
+    #[derive(Debug, PartialEq)]
+    struct Square(u8);
+    impl Shape for Square {
+        type Area = u16;
+        fn area(&self) -> u16 {
+            self.0 as u16 * self.0 as u16
+        }
+        fn scaled(&self, factor: u8) -> Self {
+            Square(self.0 * factor)
+        }
+    }
+    #[derive(Debug, PartialEq)]
+    struct Circle(f32);
+    impl Shape for Circle {
+        type Area = f32;
+        fn area(&self) -> f32 {
+            3.0 * self.0 * self.0
+        }
+        fn scaled(&self, factor: u8) -> Self {
+            Circle(self.0 * factor as f32)
+        }
+    }
+
+    TraitHList! {
+        ShapeHList for trait Shape {
+            #[hlist(at)]
+            fn area(&self) -> Self::Area;
+            #[hlist(at)]
+            fn scaled(&self, factor: u8) -> Self;
+        }
+    }
+
+    let shapes = hlist![Square(2), Circle(1.0), Square(3)];
+    let area: u16 = shapes.area_at::<Here>();
+    assert_eq!(area, 4);
+    let area: f32 = shapes.area_at::<There<Here>>();
+    assert_eq!(area, 3.0);
+    assert_eq!(shapes.scaled_at::<There<There<Here>>>(2), Square(6));
+    assert_eq!(shapes.scaled_at::<There<Here>>(2), Circle(2.0));
+}
+
+#[test]
+fn typed_index_access_on_exported_signatures() {
+    TraitHList! {
+        NamesHList for trait Component use __component_sigs {
+            #[name = names]
+            #[hlist(at)]
+            fn name;
+        }
+    }
+
+    let list = hlist![Fixed(false, "timer"), Fixed(true, "counter")];
+    assert_eq!(list.names_at::<hlist2::ops::There<hlist2::ops::Here>>(), "counter");
+}
+
+#[test]
+fn index_dispatch_on_long_lists() {
+    pub trait Id {
+        fn id(&self) -> usize;
+    }
+    impl Id for usize {
+        fn id(&self) -> usize {
+            *self
+        }
+    }
+    impl Id for u8 {
+        fn id(&self) -> usize {
+            *self as usize
+        }
+    }
+
+    TraitHList! {
+        IdHList for trait Id {
+            #[hlist(get)]
+            fn id(&self) -> usize;
+        }
+    }
+
+    let list = hlist![0usize, 1u8, 2usize, 3u8, 4usize, 5u8, 6usize, 7u8, 8usize, 9u8, 10usize, 11u8, 12usize, 13u8, 14usize, 15u8];
+    for index in 0..16 {
+        assert_eq!(list.id_at_index(index), index);
+        assert_eq!(list.get_id(index), Some(index));
+    }
+    assert_eq!(list.get_id(16), None);
+    assert_eq!(list.get_id(usize::MAX), None);
+    assert_eq!(list.id_iter().collect::<Vec<_>>(), (0..16).collect::<Vec<_>>());
+}
+
+#[test]
+fn batched_index_calls() {
+    pub trait Scale {
+        fn scale(&self, factor: u32) -> u32;
+    }
+    impl Scale for u32 {
+        fn scale(&self, factor: u32) -> u32 {
+            self * factor
+        }
+    }
+    impl Scale for u8 {
+        fn scale(&self, factor: u32) -> u32 {
+            *self as u32 * factor * 100
+        }
+    }
+
+    TraitHList! {
+        ScaleHList for trait Scale {
+            fn scale(&self, factor: u32) -> u32;
+        }
+    }
+
+    let list = hlist![1u32, 2u8, 3u32];
+    assert_eq!(list.scale_at_indices(2, [2, 0, 1, 0]), vec![6, 2, 400, 2]);
+    assert_eq!(list.scale_at_indices(1, 0..3), vec![1, 200, 3]);
+    assert_eq!(list.scale_at_indices(1, []), Vec::<u32>::new());
+}
+
+#[test]
+fn batched_index_calls_on_exported_signatures() {
+    TraitHList! { ComponentHList for trait Component use __component_sigs; }
+    TraitHList! {
+        NamesHList for trait Component use __component_sigs {
+            #[name = names]
+            fn name;
+        }
+    }
+
+    let mut list = hlist![Fixed(false, "timer"), Fixed(true, "counter")];
+    assert_eq!(list.update_at_indices(0.5, [1]), vec![true]);
+    assert_eq!(list.name_at_indices([1, 0]), list.names_at_indices([1, 0]));
+}
+
+#[test]
+fn batched_index_calls_with_mutable_arguments() {
+    pub trait Push<T> {
+        fn push(&mut self, v: &mut Vec<T>);
+    }
+
+    impl Push<i32> for i32 {
+        fn push(&mut self, v: &mut Vec<i32>) {
+            *self += 10;
+            v.push(*self);
+        }
+    }
+
+    TraitHList! {
+        PushHList for trait Push<T> {
+            fn push(&mut self, v: &mut Vec<T>);
+        }
+    }
+
+    let mut v = vec![];
+    let mut h = hlist![0, 1, 2, 3];
+    h.push_at_indices(&mut v, [3, 0, 3]);
+    assert_eq!(v, [13, 10, 23]);
+    assert_eq!(h, hlist![10, 1, 2, 23]);
+}
+
+#[test]
+fn reverse_evaluation() {
+    use std::cell::RefCell;
+
+    pub trait Layer {
+        fn shutdown(&mut self, log: &mut Vec<&'static str>) -> usize;
+        fn close(&mut self, log: &mut Vec<&'static str>);
+        fn handles(&self, calls: &RefCell<Vec<&'static str>>) -> bool;
+    }
+    struct Named(&'static str, bool);
+    impl Layer for Named {
+        fn shutdown(&mut self, log: &mut Vec<&'static str>) -> usize {
+            log.push(self.0);
+            log.len()
+        }
+        fn close(&mut self, log: &mut Vec<&'static str>) {
+            log.push(self.0);
+        }
+        fn handles(&self, calls: &RefCell<Vec<&'static str>>) -> bool {
+            calls.borrow_mut().push(self.0);
+            self.1
+        }
+    }
+
+    TraitHList! {
+        LayerHList for trait Layer {
+            #[hlist(rev)]
+            fn shutdown(&mut self, log: &mut Vec<&'static str>) -> usize;
+            #[hlist(rev, chain)]
+            fn close(&mut self, log: &mut Vec<&'static str>);
+            #[hlist(rev)]
+            fn handles(&self, calls: &RefCell<Vec<&'static str>>) -> bool;
+        }
+    }
+
+    let mut layers = hlist![Named("auth", true), Named("cache", false), Named("db", true)];
+    let mut log = vec![];
+    assert_eq!(layers.rev_shutdown(&mut log), hlist![3, 2, 1]);
+    assert_eq!(log, ["db", "cache", "auth"]);
+
+    log.clear();
+    layers.rev_close(&mut log).close(&mut log);
+    assert_eq!(log, ["db", "cache", "auth", "auth", "cache", "db"]);
+
+    let calls = RefCell::new(vec![]);
+    assert_eq!(layers.rev_handles(&calls), hlist![true, false, true]);
+    assert!(layers.rev_any_handles(&calls));
+    assert!(!layers.rev_all_handles(&calls));
+    assert_eq!(*calls.borrow(), ["db", "cache", "auth", "db", "db", "cache"]);
+}
+
+#[test]
+fn enumerated_arguments() {
+    pub trait Widget {
+        fn render(&self, slot: usize, out: &mut Vec<String>);
+        fn label(&self, position: u8) -> String;
+        fn placed(slot: usize) -> Self;
+    }
+    #[derive(Debug, PartialEq)]
+    struct Button(usize);
+    impl Widget for Button {
+        fn render(&self, slot: usize, out: &mut Vec<String>) {
+            out.push(format!("{slot}: button {}", self.0));
+        }
+        fn label(&self, position: u8) -> String {
+            format!("#{position}")
+        }
+        fn placed(slot: usize) -> Self {
+            Button(slot * 10)
+        }
+    }
+    #[derive(Debug, PartialEq)]
+    struct Text;
+    impl Widget for Text {
+        fn render(&self, slot: usize, out: &mut Vec<String>) {
+            out.push(format!("{slot}: text"));
+        }
+        fn label(&self, position: u8) -> String {
+            format!("text {position}")
+        }
+        fn placed(_: usize) -> Self {
+            Text
+        }
+    }
+
+    TraitHList! {
+        WidgetHList for trait Widget {
+            #[hlist(chain)]
+            fn render(&self, #[hlist(enumerate)] slot: usize, out: &mut Vec<String>);
+            fn label(&self, #[hlist(enumerate)] position: u8) -> String;
+            fn placed(#[hlist(enumerate)] slot: usize) -> Self;
+        }
+    }
+
+    let widgets = hlist![Button(1), Text, Button(2)];
+    let mut out = vec![];
+    widgets.render(&mut out).render(&mut out);
+    assert_eq!(out[..3], ["0: button 1", "1: text", "2: button 2"]);
+    assert_eq!(out[3..], out[..3]);
+    assert_eq!(widgets.label(), hlist!["#0".to_string(), "text 1".to_string(), "#2".to_string()]);
+    assert_eq!(<HList![Button, Text, Button]>::placed(), hlist![Button(0), Text, Button(20)]);
+}
+
+#[test]
+fn zipped_arguments() {
+    pub trait Stage {
+        fn process(&mut self, gain: f32, buffer: &mut Vec<f32>) -> usize;
+        fn fill(&self, slot: usize, buffer: &mut Vec<f32>);
+    }
+    struct Amp(f32);
+    impl Stage for Amp {
+        fn process(&mut self, gain: f32, buffer: &mut Vec<f32>) -> usize {
+            buffer.push(self.0 * gain);
+            buffer.len()
+        }
+        fn fill(&self, slot: usize, buffer: &mut Vec<f32>) {
+            buffer.push(slot as f32 + self.0);
+        }
+    }
+
+    TraitHList! {
+        StageHList for trait Stage {
+            fn process(&mut self, #[hlist(zip)] gain: f32, #[hlist(zip)] buffer: &mut Vec<f32>) -> usize;
+            #[hlist(chain)]
+            fn fill(&self, #[hlist(enumerate)] slot: usize, #[hlist(zip)] buffer: &mut Vec<f32>);
+        }
+    }
+
+    let mut stages = hlist![Amp(1.0), Amp(2.0), Amp(3.0)];
+    let (mut a, mut b, mut c) = (vec![], vec![0.0], vec![]);
+    assert_eq!(stages.process(hlist![0.5, 1.0, 2.0], hlist![&mut a, &mut b, &mut c]), hlist![1, 2, 1]);
+    stages.fill(hlist![&mut a, &mut b, &mut c]).fill(hlist![&mut c, &mut b, &mut a]);
+    assert_eq!((a, b, c), (vec![0.5, 1.0, 5.0], vec![0.0, 2.0, 3.0, 3.0], vec![6.0, 5.0, 1.0]));
+}
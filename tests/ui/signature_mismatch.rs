@@ -0,0 +1,13 @@
+use hlist2_trait_macro::TraitHList;
+
+trait Counter {
+    fn bump(&mut self, by: u64) -> u64;
+}
+
+TraitHList! {
+    CounterHList for trait Counter {
+        fn bump(&self, by: u32) -> u64;
+    }
+}
+
+fn main() {}